@@ -0,0 +1,85 @@
+use std::collections::BTreeMap;
+use std::fmt;
+use include_dir::{include_dir, Dir};
+use pelican_ui::Context;
+use serde::Deserialize;
+use log::debug;
+
+use crate::game_collection::Game;
+
+// Only the manifests are embedded here; the PNGs are embedded once, by `include_assets!`, and read back through `ctx.assets`.
+static MANIFESTS: Dir = include_dir!("$CARGO_MANIFEST_DIR/manifests");
+
+// A game's manifest: illustration name -> PNG file under ./assets.
+#[derive(Debug, Deserialize)]
+pub struct AssetManifest {
+    pub illustrations: BTreeMap<String, String>,
+}
+
+#[derive(Debug)]
+pub enum AssetError {
+    MissingManifest { game: &'static str, path: &'static str },
+    InvalidManifest { game: &'static str, path: &'static str, error: serde_json::Error },
+    Unregistered { game: &'static str, name: &'static str },
+}
+
+impl fmt::Display for AssetError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            AssetError::MissingManifest { game, path } => write!(f, "{}: no asset manifest at manifests/{}", game, path),
+            AssetError::InvalidManifest { game, path, error } => write!(f, "{}: manifests/{} is not a valid manifest: {}", game, path, error),
+            AssetError::Unregistered { game, name } => write!(f, "{}: sprites use illustration \"{}\", which is not in its manifest", game, name),
+        }
+    }
+}
+
+impl std::error::Error for AssetError {}
+
+impl AssetManifest {
+    // Loads a game's manifest and checks that every name the game's sprites use has an entry. That every entry has a
+    // file under ./assets is checked by the test below.
+    pub fn load(game: &dyn Game) -> Result<Self, AssetError> {
+        let (name, path) = (game.name(), game.manifest());
+        let file = MANIFESTS.get_file(path).ok_or(AssetError::MissingManifest { game: name, path })?;
+        let manifest = serde_json::from_slice::<AssetManifest>(file.contents())
+            .map_err(|error| AssetError::InvalidManifest { game: name, path, error })?;
+
+        if let Some(illustration) = game.illustrations().into_iter().find(|i| !manifest.illustrations.contains_key(*i)) {
+            return Err(AssetError::Unregistered { game: name, name: illustration });
+        }
+        Ok(manifest)
+    }
+
+    // The PNG behind one of the manifest's illustrations.
+    pub fn bytes(&self, ctx: &mut Context, name: &str) -> Option<Vec<u8>> {
+        ctx.assets.load_file(self.illustrations.get(name)?)
+    }
+
+    pub fn register(&self, ctx: &mut Context) {
+        let mut illustrations = ctx.theme.brand.illustrations.clone();
+        for (name, file) in &self.illustrations {
+            debug!(target: "assets", "registering {} from {}", name, file);
+            illustrations.insert(ctx, name, file);
+        }
+        ctx.theme.brand.illustrations = illustrations;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+    use super::AssetManifest;
+    use crate::game_collection::registry;
+
+    // A bad manifest or sprite name fails here rather than drawing blank sprites mid-game.
+    #[test]
+    fn every_game_has_a_complete_manifest() {
+        let assets = Path::new(env!("CARGO_MANIFEST_DIR")).join("assets");
+        for game in registry() {
+            let manifest = AssetManifest::load(game.as_ref()).unwrap_or_else(|e| panic!("{}", e));
+            for (name, file) in &manifest.illustrations {
+                assert!(assets.join(file).is_file(), "{}: illustration \"{}\" points to assets/{}, which is not shipped", game.name(), name, file);
+            }
+        }
+    }
+}
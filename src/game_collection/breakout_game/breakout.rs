@@ -0,0 +1,244 @@
+use pelican_ui::events::{Event, OnEvent, TickEvent};
+use pelican_ui::drawable::{Align, Drawable, Component};
+use pelican_ui::layout::{Area, SizeRequest, Layout};
+use pelican_ui::{Context, Component};
+use pelican_ui_std::{Header, Offset, ExpandableText, TextStyle, AppPage, Column, IconButton, NavigateEvent};
+use pelican_game_engine::{AspectRatio, Sprite, Gameboard, SpriteState, CollisionEvent};
+use log::{debug, info};
+
+use crate::game_collection::shared::SharedState;
+use crate::game_collection::shared::server::GameAction;
+use crate::game_collection::breakout_game::settings::BreakoutSettings;
+use crate::game_collection::breakout_game::Art;
+use crate::game_collection::launcher::Launcher;
+
+#[derive(Debug, Clone)]
+pub struct BreakoutState {
+    pub paddle: SpriteState,
+    pub velocity: (f32, f32),
+    // The ball sits on the paddle until a Shoot action launches it.
+    pub launched: bool,
+    pub bricks: Vec<String>,
+    pub lives: u32,
+    pub score: u32,
+    pub best: u32,
+    pub paused: bool,
+    pub ball_speed: f32,
+    pub paddle_speed: f32,
+    pub brick_rows: usize,
+}
+
+impl Default for BreakoutState {
+    fn default() -> Self {
+        BreakoutState {
+            paddle: SpriteState::Idle,
+            velocity: (0.0, 0.0),
+            launched: false,
+            bricks: Vec::new(),
+            lives: 3,
+            score: 0,
+            best: 0,
+            paused: false,
+            ball_speed: 3.0,
+            paddle_speed: 4.0,
+            brick_rows: 4,
+        }
+    }
+}
+
+impl BreakoutState {
+    const COLUMNS: usize = 8;
+    const BRICK_HEIGHT: f32 = 16.0;
+    const GAP: f32 = 6.0;
+
+    fn launch(&mut self) {
+        if self.launched { return; }
+        self.launched = true;
+        self.velocity = (self.ball_speed * 0.5, -self.ball_speed);
+    }
+
+    fn build_bricks(&mut self, ctx: &mut Context, gameboard: &mut Gameboard) {
+        let width = gameboard.0.size(ctx).0;
+        let brick_width = (width - Self::GAP * (Self::COLUMNS as f32 + 1.0)) / Self::COLUMNS as f32;
+        for row in 0..self.brick_rows {
+            for col in 0..Self::COLUMNS {
+                let id = format!("brick_{}_{}", row, col);
+                let x = Self::GAP + col as f32 * (brick_width + Self::GAP);
+                let y = 40.0 + row as f32 * (Self::BRICK_HEIGHT + Self::GAP);
+                let brick = Sprite::new(ctx, &id, Art::Brick.name(), (brick_width, Self::BRICK_HEIGHT), (Offset::Static(x), Offset::Static(y)));
+                gameboard.insert_sprite(ctx, brick);
+                self.bricks.push(id);
+            }
+        }
+        debug!(target: "breakout", "built a wall of {} bricks", self.bricks.len());
+    }
+
+    fn lose_ball(&mut self, gameboard: &mut Gameboard) {
+        self.launched = false;
+        self.lives = self.lives.saturating_sub(1);
+        info!(target: "breakout", "Ball lost, remaining lives: {}", self.lives);
+        if self.lives == 0 {
+            info!(target: "breakout", "Game over with score {}", self.score);
+            self.best = self.best.max(self.score);
+            self.score = 0;
+            self.lives = 3;
+            self.bricks.drain(..).for_each(|id| gameboard.remove_sprite_by_id(&id));
+        }
+    }
+}
+
+// Fields: layout, header, score text, gameboard
+#[derive(Debug, Component)]
+pub struct Breakout(Column, Header, ExpandableText, Option<Gameboard>);
+impl OnEvent for Breakout {
+    fn on_event(&mut self, ctx: &mut Context, event: &mut dyn Event) -> bool {
+        if let Some(TickEvent) = event.downcast_ref::<TickEvent>() {
+            let state = ctx.state().get_mut_or_default::<BreakoutState>();
+            let score = format!("SCORE: {}   LIVES: {}   BEST: {}", state.score, state.lives, state.best);
+            self.2.text().spans[0].text = if state.paused { format!("PAUSED - {}", score) } else { score };
+        }
+        true
+    }
+}
+
+impl AppPage for Breakout {
+    fn has_nav(&self) -> bool {false}
+    fn navigate(mut self: Box<Self>, ctx: &mut Context, index: usize) -> Result<Box<dyn AppPage>, Box<dyn AppPage>> {
+        match index {
+            0 => Ok(Box::new(BreakoutSettings::new(ctx, self.3.take().unwrap(), |ctx, gameboard| Box::new(Breakout::new(ctx, Some(gameboard)))))),
+            1 => Ok(Box::new(Launcher::new(ctx))),
+            _ => Err(self)
+        }
+    }
+}
+
+impl Breakout {
+    pub fn new(ctx: &mut Context, gameboard: Option<Gameboard>) -> Self {
+        // Played with the same controllers and server as the other games, started here if none has run yet.
+        SharedState::ensure(ctx);
+        let mut gameboard = gameboard.unwrap_or_else(|| Self::gameboard(ctx));
+
+        // A fresh board (first game, or back from the launcher) needs its paddle and ball, and a new wall on the next tick.
+        if gameboard.get_sprite_by_id("paddle").is_none() {
+            let paddle = Sprite::new(ctx, "paddle", Art::Paddle.name(), (80.0, 12.0), (Offset::Center, Offset::End));
+            gameboard.insert_sprite(ctx, paddle);
+            let ball = Sprite::new(ctx, "ball", Art::Ball.name(), (16.0, 16.0), (Offset::Center, Offset::Center));
+            gameboard.insert_sprite(ctx, ball);
+            let state = ctx.state().get_mut_or_default::<BreakoutState>();
+            state.bricks.clear();
+            state.launched = false;
+        }
+
+        let settings = IconButton::navigation(ctx, "settings", |ctx: &mut Context| ctx.trigger_event(NavigateEvent(0)));
+        let back = IconButton::navigation(ctx, "left", |ctx: &mut Context| ctx.trigger_event(NavigateEvent(1)));
+        let header = Header::stack(ctx, Some(back), "Breakout", Some(settings));
+        let text_size = ctx.theme.fonts.size.h3;
+        let text = ExpandableText::new(ctx, "SCORE: 0", TextStyle::Heading, text_size, Align::Center, None);
+        Breakout(Column::center(24.0), header, text, Some(gameboard))
+    }
+
+    pub fn gameboard(ctx: &mut Context) -> Gameboard {
+        Gameboard::new(ctx, AspectRatio::OneOne, Box::new(Self::on_event))
+    }
+
+    fn on_event(gameboard: &mut Gameboard, ctx: &mut Context, event: &mut dyn Event) -> bool {
+        ctx.state().get_mut_or_default::<SharedState>().route_input(event);
+
+        if let Some(TickEvent) = event.downcast_ref::<TickEvent>() {
+            let actions = ctx.state().get_mut_or_default::<SharedState>().take_actions();
+            let mut state = ctx.state().get_mut_or_default::<BreakoutState>().clone();
+            let mut open_settings = false;
+
+            for action in actions {
+                match action {
+                    GameAction::MoveLeft => state.paddle = SpriteState::MovingLeft,
+                    GameAction::MoveRight => state.paddle = SpriteState::MovingRight,
+                    GameAction::StopMoving => state.paddle = SpriteState::Idle,
+                    GameAction::Shoot => state.launch(),
+                    GameAction::Pause => state.paused = !state.paused,
+                    GameAction::OpenSettings => open_settings = true,
+                }
+            }
+
+            if open_settings || state.paused {
+                ctx.state().set(state);
+                if open_settings { ctx.trigger_event(NavigateEvent(0)); }
+                return true;
+            }
+
+            if state.bricks.is_empty() {
+                state.launched = false;
+                state.build_bricks(ctx, gameboard);
+            }
+
+            let (width, height) = gameboard.0.size(ctx);
+            if let Some(paddle) = gameboard.get_sprite_by_id("paddle") {
+                let x = paddle.position(ctx).0;
+                match state.paddle {
+                    SpriteState::MovingLeft if x > 0.0 => paddle.adjustments().0 -= state.paddle_speed,
+                    SpriteState::MovingRight if x < width - paddle.dimensions().0 => paddle.adjustments().0 += state.paddle_speed,
+                    _ => {}
+                }
+            }
+            let paddle = gameboard.get_sprite_by_id("paddle").map(|p| (p.position(ctx), p.dimensions().clone()));
+
+            let mut lost = false;
+            if let Some(ball) = gameboard.get_sprite_by_id("ball") {
+                let (x, y) = ball.position(ctx);
+                let (w, h) = ball.dimensions().clone();
+                match state.launched {
+                    // Ride on top of the paddle until launched.
+                    false => if let Some(((px, py), (pw, _))) = paddle {
+                        ball.adjustments().0 += (px + pw / 2.0 - w / 2.0) - x;
+                        ball.adjustments().1 += (py - h - 2.0) - y;
+                    },
+                    true => {
+                        if x <= 0.0 { state.velocity.0 = state.velocity.0.abs(); }
+                        if x + w >= width { state.velocity.0 = -state.velocity.0.abs(); }
+                        if y <= 0.0 { state.velocity.1 = state.velocity.1.abs(); }
+                        ball.adjustments().0 += state.velocity.0;
+                        ball.adjustments().1 += state.velocity.1;
+                        lost = y > height;
+                    }
+                }
+            }
+            if lost { state.lose_ball(gameboard); }
+
+            ctx.state().set(state);
+
+            gameboard.2.iter_mut().enumerate().for_each(|(i, s)| {
+                if let Some(location) = gameboard.0.0.get_mut(i+1) {
+                    let (x, y) = s.position(ctx);
+                    location.0 = Offset::Static(x);
+                    location.1 = Offset::Static(y);
+                }
+            });
+
+        } else if let Some(CollisionEvent(a, b)) = event.downcast_ref::<CollisionEvent>() {
+            let other = match (a.as_str(), b.as_str()) {
+                ("ball", other) | (other, "ball") => other.to_string(),
+                _ => return true,
+            };
+            let ball = gameboard.get_sprite_by_id("ball").map(|s| s.position(ctx).0 + s.dimensions().0 / 2.0);
+            if other == "paddle" {
+                // Where the ball lands on the paddle sets its angle, so the player can aim.
+                let paddle = gameboard.get_sprite_by_id("paddle").map(|s| (s.position(ctx).0 + s.dimensions().0 / 2.0, s.dimensions().0 / 2.0));
+                let state = ctx.state().get_mut_or_default::<BreakoutState>();
+                if let (Some(ball), Some((center, half))) = (ball, paddle) {
+                    let offset = ((ball - center) / half).clamp(-1.0, 1.0);
+                    state.velocity = (state.ball_speed * offset, -state.ball_speed);
+                }
+            } else if other.starts_with("brick_") {
+                let state = ctx.state().get_mut_or_default::<BreakoutState>();
+                if state.bricks.contains(&other) {
+                    debug!(target: "breakout", "ball broke {}", other);
+                    state.bricks.retain(|b| *b != other);
+                    state.velocity.1 = -state.velocity.1;
+                    state.score += 100;
+                    gameboard.remove_sprite_by_id(&other);
+                }
+            }
+        }
+        true
+    }
+}
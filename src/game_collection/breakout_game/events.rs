@@ -0,0 +1,26 @@
+use pelican_ui::events::Event;
+use pelican_ui::Context;
+
+#[derive(Clone, Debug)]
+pub struct AdjustBallSpeed(pub f32);
+impl Event for AdjustBallSpeed {
+    fn pass(self: Box<Self>, _ctx: &mut Context, children: Vec<((f32, f32), (f32, f32))>) -> Vec<Option<Box<dyn Event>>> {
+        children.into_iter().map(|_| Some(self.clone() as Box<dyn Event>)).collect()
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct AdjustPaddleSpeed(pub f32);
+impl Event for AdjustPaddleSpeed {
+    fn pass(self: Box<Self>, _ctx: &mut Context, children: Vec<((f32, f32), (f32, f32))>) -> Vec<Option<Box<dyn Event>>> {
+        children.into_iter().map(|_| Some(self.clone() as Box<dyn Event>)).collect()
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct AdjustBrickRows(pub i32);
+impl Event for AdjustBrickRows {
+    fn pass(self: Box<Self>, _ctx: &mut Context, children: Vec<((f32, f32), (f32, f32))>) -> Vec<Option<Box<dyn Event>>> {
+        children.into_iter().map(|_| Some(self.clone() as Box<dyn Event>)).collect()
+    }
+}
\ No newline at end of file
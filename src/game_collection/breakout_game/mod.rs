@@ -0,0 +1,50 @@
+pub mod breakout;
+mod settings;
+mod events;
+
+
+use pelican_ui::Context;
+use pelican_ui_std::AppPage;
+
+use crate::game_collection::Game;
+
+// Illustrations Breakout's sprites are drawn with; names must match manifests/breakout.json.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Art {
+    Paddle,
+    Ball,
+    Brick,
+}
+
+impl Art {
+    pub const ALL: [Art; 3] = [Art::Paddle, Art::Ball, Art::Brick];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Art::Paddle => "paddle",
+            Art::Ball => "ball",
+            Art::Brick => "brick",
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct BreakoutGame;
+
+impl Game for BreakoutGame {
+    fn name(&self) -> &'static str {"Breakout"}
+    fn description(&self) -> &'static str {"Keep the ball in play with the paddle and clear the wall of bricks."}
+    fn icon(&self) -> &'static str {"right"}
+
+    fn manifest(&self) -> &'static str {"breakout.json"}
+    fn illustrations(&self) -> Vec<&'static str> {Art::ALL.iter().map(|a| a.name()).collect()}
+
+    fn page(&self, ctx: &mut Context) -> Box<dyn AppPage> {
+        Box::new(breakout::Breakout::new(ctx, None))
+    }
+
+    fn settings(&self, ctx: &mut Context) -> Option<Box<dyn AppPage>> {
+        let gameboard = breakout::Breakout::gameboard(ctx);
+        Some(Box::new(settings::BreakoutSettings::new(ctx, gameboard, crate::game_collection::to_launcher)))
+    }
+}
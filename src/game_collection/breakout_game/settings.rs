@@ -0,0 +1,71 @@
+use pelican_ui::events::{Event, OnEvent};
+use pelican_ui::drawable::{Drawable, Component};
+use pelican_ui::layout::{Area, SizeRequest, Layout};
+use pelican_ui::{Context, Component};
+use pelican_ui_std::{Stack, Content, Header, Page, Button, Offset, AppPage, IconButton, NavigateEvent, DataItem};
+use pelican_game_engine::Gameboard;
+
+use crate::game_collection::breakout_game::events::{AdjustBallSpeed, AdjustPaddleSpeed, AdjustBrickRows};
+use crate::game_collection::ReturnTo;
+use crate::game_collection::breakout_game::breakout::BreakoutState;
+
+#[derive(Debug, Component)]
+pub struct BreakoutSettings(Stack, Page, #[skip] Option<Gameboard>, #[skip] ReturnTo);
+
+impl OnEvent for BreakoutSettings {
+    fn on_event(&mut self, ctx: &mut Context, event: &mut dyn Event) -> bool {
+        if let Some(AdjustBallSpeed(d)) = event.downcast_ref::<AdjustBallSpeed>() {
+            let state = ctx.state().get_mut_or_default::<BreakoutState>();
+            state.ball_speed = (state.ball_speed + d).clamp(1.0, 8.0);
+            *self.1.content().find_at::<DataItem>(0).unwrap().label() = format!("Ball Speed: {:.1}", state.ball_speed);
+        } else if let Some(AdjustPaddleSpeed(d)) = event.downcast_ref::<AdjustPaddleSpeed>() {
+            let state = ctx.state().get_mut_or_default::<BreakoutState>();
+            state.paddle_speed = (state.paddle_speed + d).clamp(1.0, 10.0);
+            *self.1.content().find_at::<DataItem>(1).unwrap().label() = format!("Paddle Speed: {:.1}", state.paddle_speed);
+        } else if let Some(AdjustBrickRows(n)) = event.downcast_ref::<AdjustBrickRows>() {
+            let state = ctx.state().get_mut_or_default::<BreakoutState>();
+            state.brick_rows = (state.brick_rows as i32 + n).clamp(1, 8) as usize;
+            *self.1.content().find_at::<DataItem>(2).unwrap().label() = format!("Brick Rows: {}", state.brick_rows);
+        }
+        true
+    }
+}
+
+impl AppPage for BreakoutSettings {
+    fn has_nav(&self) -> bool {false}
+    fn navigate(mut self: Box<Self>, ctx: &mut Context, index: usize) -> Result<Box<dyn AppPage>, Box<dyn AppPage>> {
+        match index {
+            0 => Ok((self.3)(ctx, self.2.take().unwrap())),
+            _ => Err(self)
+        }
+    }
+}
+
+impl BreakoutSettings {
+    pub fn new(ctx: &mut Context, gameboard: Gameboard, back: ReturnTo) -> Self {
+        let state = ctx.state().get_mut_or_default::<BreakoutState>().clone();
+
+        let items: [(String, &str, f32, Box<dyn Fn(f32) -> Box<dyn FnMut(&mut Context)>>); 3] = [
+            (format!("Ball Speed: {:.1}", state.ball_speed), "How fast the ball travels after launch.", 0.5,
+                Box::new(|d| Box::new(move |ctx: &mut Context| ctx.trigger_event(AdjustBallSpeed(d))))),
+            (format!("Paddle Speed: {:.1}", state.paddle_speed), "How fast the paddle slides while a move is held.", 0.5,
+                Box::new(|d| Box::new(move |ctx: &mut Context| ctx.trigger_event(AdjustPaddleSpeed(d))))),
+            (format!("Brick Rows: {}", state.brick_rows), "Rows in the next wall of bricks.", 1.0,
+                Box::new(|d| Box::new(move |ctx: &mut Context| ctx.trigger_event(AdjustBrickRows(d as i32))))),
+        ];
+        let items = items.into_iter().map(|(title, sub, step, event)| {
+            let buttons = vec![
+                Button::secondary(ctx, Some("add"), "Decrease", None, event(-step), None),
+                Button::secondary(ctx, Some("add"), "Increase", None, event(step), None),
+            ];
+            DataItem::new(ctx, None, &title, Some(sub), None, None, Some(buttons))
+        }).collect::<Vec<_>>();
+
+        let back_button = IconButton::navigation(ctx, "left", |ctx: &mut Context| ctx.trigger_event(NavigateEvent(0)));
+        let header = Header::stack(ctx, Some(back_button), "Breakout Settings", None);
+        let drawable_items: Vec<Box<dyn Drawable>> = items.into_iter().map(|item| Box::new(item) as Box<dyn Drawable>).collect();
+        let content = Content::new(Offset::Start, drawable_items);
+
+        BreakoutSettings(Stack::default(), Page::new(Some(header), content, None), Some(gameboard), back)
+    }
+}
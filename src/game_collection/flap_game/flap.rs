@@ -0,0 +1,301 @@
+use pelican_ui::events::{Event, OnEvent, TickEvent};
+use pelican_ui::drawable::{Align, Drawable, Component};
+use pelican_ui::layout::{Area, SizeRequest, Layout};
+use pelican_ui::{Context, Component};
+use pelican_ui_std::{Header, Offset, ExpandableText, TextStyle, AppPage, Column, IconButton, NavigateEvent};
+use pelican_game_engine::{AspectRatio, Sprite, Gameboard, CollisionEvent};
+use rand::Rng;
+use log::{debug, info};
+
+use crate::game_collection::shared::SharedState;
+use crate::game_collection::shared::metrics::SessionMetrics;
+use crate::game_collection::shared::report::ReportPage;
+use crate::game_collection::shared::server::GameAction;
+use crate::game_collection::shared::profiles::Profile;
+use crate::game_collection::flap_game::settings::FlapSettings;
+use crate::game_collection::flap_game::Art;
+use crate::game_collection::launcher::Launcher;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Difficulty {
+    Easy,
+    Normal,
+    Hard,
+}
+
+impl Difficulty {
+    pub fn label(&self) -> &'static str {
+        match self {
+            Difficulty::Easy => "Easy",
+            Difficulty::Normal => "Normal",
+            Difficulty::Hard => "Hard",
+        }
+    }
+
+    pub fn next(&self) -> Self {
+        match self {
+            Difficulty::Easy => Difficulty::Normal,
+            Difficulty::Normal => Difficulty::Hard,
+            Difficulty::Hard => Difficulty::Easy,
+        }
+    }
+
+    // Height of the opening in each pair of pipes.
+    pub fn gap(&self) -> f32 {
+        match self {
+            Difficulty::Easy => 200.0,
+            Difficulty::Normal => 150.0,
+            Difficulty::Hard => 110.0,
+        }
+    }
+
+    // How far the pipes move each tick.
+    pub fn speed(&self) -> f32 {
+        match self {
+            Difficulty::Easy => 1.5,
+            Difficulty::Normal => 2.0,
+            Difficulty::Hard => 2.75,
+        }
+    }
+
+    pub fn gravity(&self) -> f32 {
+        match self {
+            Difficulty::Easy => 0.15,
+            Difficulty::Normal => 0.2,
+            Difficulty::Hard => 0.25,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct FlapState {
+    pub velocity: f32,
+    // Nothing moves until the first flap.
+    pub started: bool,
+    pub over: bool,
+    // Fields: pipe pair number, whether the bird has passed it
+    pub pipes: Vec<(usize, bool)>,
+    pub next_pipe: usize,
+    pub score: u32,
+    pub best: u32,
+    pub paused: bool,
+    pub difficulty: Difficulty,
+}
+
+impl Default for FlapState {
+    fn default() -> Self {
+        FlapState {
+            velocity: 0.0,
+            started: false,
+            over: false,
+            pipes: Vec::new(),
+            next_pipe: 0,
+            score: 0,
+            best: 0,
+            paused: false,
+            difficulty: Difficulty::Normal,
+        }
+    }
+}
+
+impl FlapState {
+    const FLAP: f32 = 4.5;
+    const PIPE_WIDTH: f32 = 60.0;
+    // Horizontal distance between pipe pairs.
+    const SPACING: f32 = 220.0;
+    const BIRD_X: f32 = 60.0;
+
+    // Starts the shared inputs if no game has yet, and a fresh "Flap" session in the shared metrics.
+    pub fn connect(ctx: &mut Context) {
+        SharedState::ensure(ctx);
+        let shared = ctx.state().get_mut_or_default::<SharedState>();
+        shared.record(|m| *m = SessionMetrics::new("Flap"));
+        let best = shared.profile.as_ref().and_then(|name| Profile::load(name)).map(|p| p.best_score("Flap")).unwrap_or(0);
+        let state = ctx.state().get_mut_or_default::<FlapState>();
+        state.best = state.best.max(best);
+    }
+
+    fn spawn_pipes(&mut self, ctx: &mut Context, gameboard: &mut Gameboard) {
+        let (width, height) = gameboard.0.size(ctx);
+        let gap = self.difficulty.gap();
+        let center = rand::thread_rng().gen_range((gap / 2.0 + 20.0)..(height - gap / 2.0 - 20.0).max(gap / 2.0 + 21.0));
+        let n = self.next_pipe;
+        self.next_pipe += 1;
+        let top = Sprite::new(ctx, &format!("pipe_top_{}", n), Art::Pipe.name(), (Self::PIPE_WIDTH, center - gap / 2.0), (Offset::Static(width), Offset::Static(0.0)));
+        gameboard.insert_sprite(ctx, top);
+        let bottom_y = center + gap / 2.0;
+        let bottom = Sprite::new(ctx, &format!("pipe_bottom_{}", n), Art::Pipe.name(), (Self::PIPE_WIDTH, height - bottom_y), (Offset::Static(width), Offset::Static(bottom_y)));
+        gameboard.insert_sprite(ctx, bottom);
+        self.pipes.push((n, false));
+        debug!(target: "flap", "spawned pipe pair {} with the gap at {:.0}", n, center);
+    }
+
+    fn clear(&mut self, gameboard: &mut Gameboard) {
+        for (n, _) in self.pipes.drain(..) {
+            gameboard.remove_sprite_by_id(&format!("pipe_top_{}", n));
+            gameboard.remove_sprite_by_id(&format!("pipe_bottom_{}", n));
+        }
+        gameboard.remove_sprite_by_id("bird");
+        self.velocity = 0.0;
+        self.started = false;
+        self.over = false;
+        self.score = 0;
+        self.paused = false;
+    }
+}
+
+// Fields: layout, header, score text, gameboard
+#[derive(Debug, Component)]
+pub struct Flap(Column, Header, ExpandableText, Option<Gameboard>);
+impl OnEvent for Flap {
+    fn on_event(&mut self, ctx: &mut Context, event: &mut dyn Event) -> bool {
+        if let Some(TickEvent) = event.downcast_ref::<TickEvent>() {
+            let state = ctx.state().get_mut_or_default::<FlapState>();
+            let score = format!("SCORE: {}   BEST: {}", state.score, state.best);
+            self.2.text().spans[0].text = match (state.paused, state.started) {
+                (true, _) => format!("PAUSED - {}", score),
+                (false, false) => "FLAP TO START".to_string(),
+                (false, true) => score,
+            };
+        }
+        true
+    }
+}
+
+impl AppPage for Flap {
+    fn has_nav(&self) -> bool {false}
+    fn navigate(mut self: Box<Self>, ctx: &mut Context, index: usize) -> Result<Box<dyn AppPage>, Box<dyn AppPage>> {
+        match index {
+            0 => Ok(Box::new(FlapSettings::new(ctx, self.3.take().unwrap(), |ctx, gameboard| Box::new(Flap::new(ctx, Some(gameboard)))))),
+            1 => Ok(Box::new(Launcher::new(ctx))),
+            2 => Ok(Box::new(ReportPage::new(ctx, self.3.take().unwrap(), |ctx, gameboard| Box::new(Flap::new(ctx, Some(gameboard)))))),
+            _ => Err(self)
+        }
+    }
+}
+
+impl Flap {
+    pub fn new(ctx: &mut Context, gameboard: Option<Gameboard>) -> Self {
+        FlapState::connect(ctx);
+        let mut gameboard = gameboard.unwrap_or_else(|| Self::gameboard(ctx));
+
+        // Every visit starts a new run, including coming back from settings or the report.
+        ctx.state().get_mut_or_default::<FlapState>().clear(&mut gameboard);
+        let bird = Sprite::new(ctx, "bird", Art::Bird.name(), (32.0, 32.0), (Offset::Static(FlapState::BIRD_X), Offset::Center));
+        gameboard.insert_sprite(ctx, bird);
+
+        let settings = IconButton::navigation(ctx, "settings", |ctx: &mut Context| ctx.trigger_event(NavigateEvent(0)));
+        let back = IconButton::navigation(ctx, "left", |ctx: &mut Context| ctx.trigger_event(NavigateEvent(1)));
+        let header = Header::stack(ctx, Some(back), "Flap", Some(settings));
+        let text_size = ctx.theme.fonts.size.h3;
+        let text = ExpandableText::new(ctx, "FLAP TO START", TextStyle::Heading, text_size, Align::Center, None);
+        Flap(Column::center(24.0), header, text, Some(gameboard))
+    }
+
+    pub fn gameboard(ctx: &mut Context) -> Gameboard {
+        Gameboard::new(ctx, AspectRatio::OneOne, Box::new(Self::on_event))
+    }
+
+    fn on_event(gameboard: &mut Gameboard, ctx: &mut Context, event: &mut dyn Event) -> bool {
+        ctx.state().get_mut_or_default::<SharedState>().route_input(event);
+
+        if let Some(TickEvent) = event.downcast_ref::<TickEvent>() {
+            let actions = ctx.state().get_mut_or_default::<SharedState>().take_actions();
+            let mut state = ctx.state().get_mut_or_default::<FlapState>().clone();
+            let mut open_settings = false;
+
+            // Only the shoot channel plays; movement from the controllers is ignored so a single sensor is enough.
+            for action in actions {
+                ctx.state().get_mut_or_default::<SharedState>().record(|m| m.action(&action));
+                match action {
+                    GameAction::Shoot if !state.over => {
+                        state.started = true;
+                        state.velocity = -FlapState::FLAP;
+                    }
+                    GameAction::Pause => state.paused = !state.paused,
+                    GameAction::OpenSettings => open_settings = true,
+                    _ => {}
+                }
+            }
+
+            if open_settings || state.paused || !state.started || state.over {
+                ctx.state().set(state);
+                if open_settings { ctx.trigger_event(NavigateEvent(0)); }
+                return true;
+            }
+
+            let (width, height) = gameboard.0.size(ctx);
+            let speed = state.difficulty.speed();
+            let last = state.pipes.last().and_then(|(n, _)| gameboard.get_sprite_by_id(&format!("pipe_top_{}", n)).map(|p| p.position(ctx).0));
+            if last.is_none_or(|x| x < width - FlapState::SPACING) {
+                state.spawn_pipes(ctx, gameboard);
+            }
+
+            let mut passed = 0;
+            state.pipes.retain_mut(|(n, scored)| {
+                let mut x = None;
+                for id in [format!("pipe_top_{}", n), format!("pipe_bottom_{}", n)] {
+                    if let Some(pipe) = gameboard.get_sprite_by_id(&id) {
+                        pipe.adjustments().0 -= speed;
+                        x = Some(pipe.position(ctx).0);
+                    }
+                }
+                let Some(x) = x else { return false };
+                if !*scored && x + FlapState::PIPE_WIDTH < FlapState::BIRD_X {
+                    *scored = true;
+                    passed += 1;
+                }
+                if x + FlapState::PIPE_WIDTH < 0.0 {
+                    gameboard.remove_sprite_by_id(&format!("pipe_top_{}", n));
+                    gameboard.remove_sprite_by_id(&format!("pipe_bottom_{}", n));
+                    return false;
+                }
+                true
+            });
+            state.score += passed;
+
+            state.velocity += state.difficulty.gravity();
+            let mut crashed = false;
+            if let Some(bird) = gameboard.get_sprite_by_id("bird") {
+                let (_, y) = bird.position(ctx);
+                let h = bird.dimensions().1;
+                if y + state.velocity < 0.0 {
+                    state.velocity = 0.0;
+                }
+                bird.adjustments().1 += state.velocity;
+                crashed = y + h >= height;
+            }
+
+            ctx.state().set(state);
+            if crashed { Self::crash(ctx); }
+
+            gameboard.2.iter_mut().enumerate().for_each(|(i, s)| {
+                if let Some(location) = gameboard.0.0.get_mut(i+1) {
+                    let (x, y) = s.position(ctx);
+                    location.0 = Offset::Static(x);
+                    location.1 = Offset::Static(y);
+                }
+            });
+
+        } else if let Some(CollisionEvent(a, b)) = event.downcast_ref::<CollisionEvent>() {
+            if (a == "bird" && b.starts_with("pipe_")) || (b == "bird" && a.starts_with("pipe_")) {
+                Self::crash(ctx);
+            }
+        }
+        true
+    }
+
+    // Ends the run and hands the session to the shared report and profile history.
+    fn crash(ctx: &mut Context) {
+        let state = ctx.state().get_mut_or_default::<FlapState>();
+        if state.over { return; }
+        state.over = true;
+        state.best = state.best.max(state.score);
+        let score = state.score;
+        info!(target: "flap", "Crashed with score {}", score);
+        let shared = ctx.state().get_mut_or_default::<SharedState>();
+        shared.record(|m| m.end(score));
+        shared.save_session();
+        ctx.trigger_event(NavigateEvent(2));
+    }
+}
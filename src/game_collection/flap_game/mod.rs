@@ -0,0 +1,48 @@
+pub mod flap;
+mod settings;
+mod events;
+
+
+use pelican_ui::Context;
+use pelican_ui_std::AppPage;
+
+use crate::game_collection::Game;
+
+// Illustrations Flap's sprites are drawn with; names must match manifests/flap.json.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Art {
+    Bird,
+    Pipe,
+}
+
+impl Art {
+    pub const ALL: [Art; 2] = [Art::Bird, Art::Pipe];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Art::Bird => "bird",
+            Art::Pipe => "pipe",
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct FlapGame;
+
+impl Game for FlapGame {
+    fn name(&self) -> &'static str {"Flap"}
+    fn description(&self) -> &'static str {"One button: flap to keep the bird between the pipes."}
+    fn icon(&self) -> &'static str {"right"}
+
+    fn manifest(&self) -> &'static str {"flap.json"}
+    fn illustrations(&self) -> Vec<&'static str> {Art::ALL.iter().map(|a| a.name()).collect()}
+
+    fn page(&self, ctx: &mut Context) -> Box<dyn AppPage> {
+        Box::new(flap::Flap::new(ctx, None))
+    }
+
+    fn settings(&self, ctx: &mut Context) -> Option<Box<dyn AppPage>> {
+        let gameboard = flap::Flap::gameboard(ctx);
+        Some(Box::new(settings::FlapSettings::new(ctx, gameboard, crate::game_collection::to_launcher)))
+    }
+}
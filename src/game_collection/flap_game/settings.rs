@@ -0,0 +1,54 @@
+use pelican_ui::events::{Event, OnEvent};
+use pelican_ui::drawable::{Drawable, Component};
+use pelican_ui::layout::{Area, SizeRequest, Layout};
+use pelican_ui::{Context, Component};
+use pelican_ui_std::{Stack, Content, Header, Page, Button, Offset, AppPage, IconButton, NavigateEvent, DataItem};
+use pelican_game_engine::Gameboard;
+
+use crate::game_collection::flap_game::events::CycleDifficulty;
+use crate::game_collection::ReturnTo;
+use crate::game_collection::flap_game::flap::{FlapState, Difficulty};
+
+#[derive(Debug, Component)]
+pub struct FlapSettings(Stack, Page, #[skip] Option<Gameboard>, #[skip] ReturnTo);
+
+impl OnEvent for FlapSettings {
+    fn on_event(&mut self, ctx: &mut Context, event: &mut dyn Event) -> bool {
+        if event.downcast_ref::<CycleDifficulty>().is_some() {
+            let state = ctx.state().get_mut_or_default::<FlapState>();
+            state.difficulty = state.difficulty.next();
+            let difficulty = state.difficulty;
+            let item = self.1.content().find_at::<DataItem>(0).unwrap();
+            *item.label() = difficulty_label(difficulty);
+        }
+        true
+    }
+}
+
+impl AppPage for FlapSettings {
+    fn has_nav(&self) -> bool {false}
+    fn navigate(mut self: Box<Self>, ctx: &mut Context, index: usize) -> Result<Box<dyn AppPage>, Box<dyn AppPage>> {
+        match index {
+            0 => Ok((self.3)(ctx, self.2.take().unwrap())),
+            _ => Err(self)
+        }
+    }
+}
+
+impl FlapSettings {
+    pub fn new(ctx: &mut Context, gameboard: Gameboard, back: ReturnTo) -> Self {
+        let difficulty = ctx.state().get_mut_or_default::<FlapState>().difficulty;
+        let change = Button::secondary(ctx, Some("add"), "Change", None, |ctx: &mut Context| ctx.trigger_event(CycleDifficulty), None);
+        let item = DataItem::new(ctx, None, &difficulty_label(difficulty), Some("Sets the size of the gaps, how fast the pipes come and how quickly the bird falls."), None, None, Some(vec![change]));
+
+        let back_button = IconButton::navigation(ctx, "left", |ctx: &mut Context| ctx.trigger_event(NavigateEvent(0)));
+        let header = Header::stack(ctx, Some(back_button), "Flap Settings", None);
+        let content = Content::new(Offset::Start, vec![Box::new(item) as Box<dyn Drawable>]);
+
+        FlapSettings(Stack::default(), Page::new(Some(header), content, None), Some(gameboard), back)
+    }
+}
+
+fn difficulty_label(difficulty: Difficulty) -> String {
+    format!("Difficulty: {}", difficulty.label())
+}
@@ -0,0 +1,152 @@
+use std::time::Duration;
+use pelican_game_engine::SpriteState;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ControlMode {
+    Standard,
+    // Each switch press moves the ship to the next of left, stop and right.
+    SingleSwitch,
+    // The highlight steps through left, stop and right on its own; a switch press picks the highlighted one.
+    Dwell,
+}
+
+impl ControlMode {
+    // How long each option stays highlighted in Dwell mode.
+    pub const DWELL: Duration = Duration::from_millis(1500);
+    pub const OPTIONS: [(SpriteState, &'static str); 3] = [
+        (SpriteState::MovingLeft, "LEFT"),
+        (SpriteState::Idle, "STOP"),
+        (SpriteState::MovingRight, "RIGHT"),
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            ControlMode::Standard => "Standard",
+            ControlMode::SingleSwitch => "Single Switch",
+            ControlMode::Dwell => "Dwell",
+        }
+    }
+
+    pub fn next(&self) -> Self {
+        match self {
+            ControlMode::Standard => ControlMode::SingleSwitch,
+            ControlMode::SingleSwitch => ControlMode::Dwell,
+            ControlMode::Dwell => ControlMode::Standard,
+        }
+    }
+
+    // The one input is spent on movement in these modes, so the ship fires by itself.
+    pub fn is_switch(&self) -> bool {
+        *self != ControlMode::Standard
+    }
+
+    // Option row shown under the gameboard with the highlighted option bracketed.
+    pub fn highlight(index: usize) -> String {
+        Self::OPTIONS.iter().enumerate()
+            .map(|(i, (_, label))| if i == index { format!("[ {} ]", label) } else { label.to_string() })
+            .collect::<Vec<_>>()
+            .join("    ")
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AssistPreset {
+    Off,
+    Light,
+    Full,
+    Custom,
+}
+
+impl AssistPreset {
+    pub fn label(&self) -> &'static str {
+        match self {
+            AssistPreset::Off => "Off",
+            AssistPreset::Light => "Light",
+            AssistPreset::Full => "Full",
+            AssistPreset::Custom => "Custom",
+        }
+    }
+
+    // Order the settings page steps through; Custom is only reached by changing a single assist.
+    pub fn next(&self) -> Self {
+        match self {
+            AssistPreset::Off => AssistPreset::Light,
+            AssistPreset::Light => AssistPreset::Full,
+            AssistPreset::Full | AssistPreset::Custom => AssistPreset::Off,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub enum AssistChange {
+    EnemyBulletSpeed(f32),
+    AimAssist,
+    ControlMode,
+    RespawnInvulnerability(i64),
+    GameSpeed(f32),
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Assists {
+    // Multiplier on how fast enemy missiles fall.
+    pub enemy_bullet_speed: f32,
+    // Player shots snap to the closest enemy column within reach.
+    pub aim_assist: bool,
+    // Standard, or one of the single-input schemes.
+    pub control_mode: ControlMode,
+    // Millis the ship ignores hits (and blinks) after respawning.
+    pub respawn_invulnerability: u64,
+    // Multiplier on every movement speed and enemy fire rate.
+    pub game_speed: f32,
+}
+
+impl Default for Assists {
+    fn default() -> Self {
+        Assists::preset(AssistPreset::Off)
+    }
+}
+
+impl Assists {
+    pub fn preset(preset: AssistPreset) -> Self {
+        match preset {
+            AssistPreset::Off | AssistPreset::Custom => Assists {
+                enemy_bullet_speed: 1.0,
+                aim_assist: false,
+                control_mode: ControlMode::Standard,
+                respawn_invulnerability: 1000,
+                game_speed: 1.0,
+            },
+            AssistPreset::Light => Assists {
+                enemy_bullet_speed: 0.75,
+                aim_assist: true,
+                control_mode: ControlMode::Standard,
+                respawn_invulnerability: 2000,
+                game_speed: 0.75,
+            },
+            AssistPreset::Full => Assists {
+                enemy_bullet_speed: 0.5,
+                aim_assist: true,
+                control_mode: ControlMode::SingleSwitch,
+                respawn_invulnerability: 3000,
+                game_speed: 0.5,
+            },
+        }
+    }
+
+    pub fn current_preset(&self) -> AssistPreset {
+        [AssistPreset::Off, AssistPreset::Light, AssistPreset::Full].into_iter()
+            .find(|p| Assists::preset(*p) == *self)
+            .unwrap_or(AssistPreset::Custom)
+    }
+
+    pub fn apply(&mut self, change: AssistChange) {
+        match change {
+            AssistChange::EnemyBulletSpeed(d) => self.enemy_bullet_speed = (self.enemy_bullet_speed + d).clamp(0.25, 1.5),
+            AssistChange::AimAssist => self.aim_assist = !self.aim_assist,
+            AssistChange::ControlMode => self.control_mode = self.control_mode.next(),
+            AssistChange::RespawnInvulnerability(ms) => self.respawn_invulnerability = (self.respawn_invulnerability as i64 + ms).clamp(0, 10000) as u64,
+            AssistChange::GameSpeed(d) => self.game_speed = (self.game_speed + d).clamp(0.25, 1.5),
+        }
+    }
+}
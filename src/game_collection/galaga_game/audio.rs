@@ -0,0 +1,114 @@
+use std::fmt::Debug;
+use std::sync::{Arc, Mutex};
+use serde::{Deserialize, Serialize};
+use log::{debug, trace};
+
+// Everything the game makes a noise for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Sound {
+    Shoot,
+    EnemyShoot,
+    EnemyHit,
+    PlayerHit,
+    WaveClear,
+    GameOver,
+}
+
+// Plays sounds and background music. The game only talks to `Audio`, so a platform backend can be swapped in without touching it.
+pub trait AudioBackend: Debug + Send {
+    fn play(&mut self, sound: Sound, volume: f32);
+    // Starts or stops the background music loop; called again with the new volume when it changes.
+    fn music(&mut self, playing: bool, volume: f32);
+}
+
+// Plays nothing. Used when there's no audio device, and by headless tests.
+#[derive(Debug, Default)]
+pub struct NullAudio;
+
+impl AudioBackend for NullAudio {
+    fn play(&mut self, sound: Sound, volume: f32) {
+        trace!(target: "audio", "{:?} at {:.0}%", sound, volume * 100.0);
+    }
+
+    fn music(&mut self, playing: bool, volume: f32) {
+        trace!(target: "audio", "music {} at {:.0}%", if playing {"on"} else {"off"}, volume * 100.0);
+    }
+}
+
+// The player's volume and mute, saved with their profile.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct AudioSettings {
+    pub volume: f32,
+    pub muted: bool,
+}
+
+impl Default for AudioSettings {
+    fn default() -> Self {
+        AudioSettings { volume: 0.8, muted: false }
+    }
+}
+
+impl AudioSettings {
+    pub fn volume_label(&self) -> String {
+        format!("Volume: {:.0}%", self.volume * 100.0)
+    }
+
+    pub fn mute_label(&self) -> String {
+        format!("Sound: {}", if self.muted {"Muted"} else {"On"})
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Audio {
+    backend: Arc<Mutex<Box<dyn AudioBackend>>>,
+    pub settings: AudioSettings,
+    music: bool,
+}
+
+impl Default for Audio {
+    fn default() -> Self {
+        Audio::new(Box::new(NullAudio))
+    }
+}
+
+impl Audio {
+    pub fn new(backend: Box<dyn AudioBackend>) -> Self {
+        debug!(target: "audio", "audio backend: {:?}", backend);
+        Audio { backend: Arc::new(Mutex::new(backend)), settings: AudioSettings::default(), music: false }
+    }
+
+    pub fn play(&self, sound: Sound) {
+        if self.settings.muted || self.settings.volume <= 0.0 { return; }
+        if let Ok(mut backend) = self.backend.lock() {
+            backend.play(sound, self.settings.volume);
+        }
+    }
+
+    pub fn music(&mut self, playing: bool) {
+        if self.music == playing { return; }
+        self.music = playing;
+        self.refresh_music();
+    }
+
+    pub fn set_volume(&mut self, volume: f32) {
+        self.settings.volume = volume.clamp(0.0, 1.0);
+        self.refresh_music();
+    }
+
+    pub fn toggle_mute(&mut self) {
+        self.settings.muted = !self.settings.muted;
+        self.refresh_music();
+    }
+
+    // Swaps in settings from a profile, keeping the music in step with them.
+    pub fn load(&mut self, settings: AudioSettings) {
+        self.settings = settings;
+        self.refresh_music();
+    }
+
+    fn refresh_music(&self) {
+        if let Ok(mut backend) = self.backend.lock() {
+            backend.music(self.music && !self.settings.muted, self.settings.volume);
+        }
+    }
+}
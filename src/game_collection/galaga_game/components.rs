@@ -1,3 +1,25 @@
+use pelican_ui::events::OnEvent;
+use pelican_ui::drawable::Component;
+use pelican_ui::{Context, Component};
+use pelican_ui_std::{Button, Row};
+
+use crate::game_collection::shared::events::TouchControlEvent;
+use crate::game_collection::shared::server::GameAction;
+
+// On-screen left, fire and right buttons shown under the gameboard when touch input is active.
+#[derive(Debug, Component)]
+pub struct TouchControls(Row, Button, Button, Button);
+impl OnEvent for TouchControls {}
+
+impl TouchControls {
+    pub fn new(ctx: &mut Context) -> Self {
+        let left = Button::secondary(ctx, Some("left"), "Left", None, |ctx: &mut Context| ctx.trigger_event(TouchControlEvent(GameAction::MoveLeft)), None);
+        let fire = Button::secondary(ctx, None, "Fire", None, |ctx: &mut Context| ctx.trigger_event(TouchControlEvent(GameAction::Shoot)), None);
+        let right = Button::secondary(ctx, Some("right"), "Right", None, |ctx: &mut Context| ctx.trigger_event(TouchControlEvent(GameAction::MoveRight)), None);
+        TouchControls(Row::center(16.0), left, fire, right)
+    }
+}
+
 // use std::any::Any;
 // use std::arch::aarch64::__crc32b;
 // use pelican_ui::events::{Event, Key, KeyboardEvent, KeyboardState, NamedKey, OnEvent, TickEvent};
@@ -21,4 +43,4 @@
 
 //         Box::new(DataItem::new(ctx, None, label, None, Some(description), None, Some(buttons)))
 //     }
-// }
+// }
\ No newline at end of file
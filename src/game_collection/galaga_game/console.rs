@@ -0,0 +1,104 @@
+use pelican_ui::Context;
+use pelican_game_engine::Gameboard;
+use serde_json::Value;
+use log::{info, warn};
+
+use crate::game_collection::galaga_game::galaga::GameState;
+use crate::game_collection::galaga_game::npcs::{Enemy, EnemyPatterns};
+use crate::game_collection::shared::SharedState;
+use crate::game_collection::shared::console::ack;
+
+// Most lives the lives display has room for.
+const MAX_LIVES: u32 = 5;
+
+// Admin commands QA can send over the controller WebSocket, e.g. `{"console":"lives","value":5}`.
+#[derive(Debug, Clone, Copy)]
+pub enum ConsoleCommand {
+    Spawn(EnemyPatterns),
+    SkipWave,
+    SetLives(u32),
+    SetScore(u32),
+    ToggleInvincibility,
+    FreezeEnemies,
+}
+
+impl ConsoleCommand {
+    pub fn parse(json: &Value) -> Result<ConsoleCommand, String> {
+        let name = json.get("console").and_then(|c| c.as_str()).ok_or("missing console command")?;
+        let value = || json.get("value").and_then(|v| v.as_u64()).map(|v| v.min(u32::MAX as u64) as u32).ok_or(format!("{} needs a whole number value", name));
+        match name {
+            "spawn" => {
+                let pattern = json.get("pattern").and_then(|p| p.as_str()).unwrap_or_default();
+                EnemyPatterns::from_name(pattern).map(ConsoleCommand::Spawn).ok_or(format!("unknown pattern '{}'", pattern))
+            }
+            "skip_wave" => Ok(ConsoleCommand::SkipWave),
+            "lives" => Ok(ConsoleCommand::SetLives(value()?)),
+            "score" => Ok(ConsoleCommand::SetScore(value()?)),
+            "invincible" => Ok(ConsoleCommand::ToggleInvincibility),
+            "freeze" => Ok(ConsoleCommand::FreezeEnemies),
+            _ => Err(format!("unknown console command '{}'", name)),
+        }
+    }
+
+    // Runs the command against the game on the ui thread, returning what changed.
+    pub fn apply(self, ctx: &mut Context, gameboard: &mut Gameboard) -> Result<String, String> {
+        match self {
+            ConsoleCommand::Spawn(pattern) => {
+                // Patterns number their enemies from zero, so the new wave replaces the current one.
+                clear_wave(ctx, gameboard);
+                let enemies = pattern.get(ctx, gameboard).into_iter()
+                    .map(|(s, id, frames)| Enemy::new(ctx, gameboard, s, id, frames)).collect::<Vec<Enemy>>();
+                let count = enemies.len();
+                ctx.state().get_mut_or_default::<GameState>().enemies = enemies;
+                Ok(format!("spawned {} enemies in a {} pattern", count, pattern.name()))
+            }
+            ConsoleCommand::SkipWave => {
+                let count = clear_wave(ctx, gameboard);
+                Ok(format!("removed {} enemies, next wave incoming", count))
+            }
+            ConsoleCommand::SetLives(lives) => {
+                let gamestate = ctx.state().get_mut_or_default::<GameState>();
+                let player = gamestate.player.as_mut().ok_or("no player on the board")?;
+                let lives = lives.clamp(1, MAX_LIVES);
+                player.set_lives(lives);
+                Ok(format!("lives set to {}", lives))
+            }
+            ConsoleCommand::SetScore(score) => {
+                ctx.state().get_mut_or_default::<GameState>().score = score;
+                Ok(format!("score set to {}", score))
+            }
+            ConsoleCommand::ToggleInvincibility => {
+                let gamestate = ctx.state().get_mut_or_default::<GameState>();
+                gamestate.player_invincible = !gamestate.player_invincible;
+                Ok(format!("invincibility {}", if gamestate.player_invincible {"on"} else {"off"}))
+            }
+            ConsoleCommand::FreezeEnemies => {
+                let gamestate = ctx.state().get_mut_or_default::<GameState>();
+                gamestate.enemies_frozen = !gamestate.enemies_frozen;
+                Ok(format!("enemies {}", if gamestate.enemies_frozen {"frozen"} else {"moving"}))
+            }
+        }
+    }
+}
+
+// Removes every enemy without scoring them; the tick spawns a fresh wave once none are left.
+fn clear_wave(ctx: &mut Context, gameboard: &mut Gameboard) -> usize {
+    let enemies = std::mem::take(&mut ctx.state().get_mut_or_default::<GameState>().enemies);
+    enemies.iter().for_each(|e| gameboard.remove_sprite_by_id(&e.id()));
+    enemies.len()
+}
+
+// Applies everything the server has queued since the last tick. Runs even while paused so QA can set up a scene.
+pub fn run_queued(ctx: &mut Context, gameboard: &mut Gameboard) {
+    let Some(queue) = ctx.state().get_mut_or_default::<SharedState>().console.clone() else { return };
+    let messages = queue.lock().map(|mut q| q.drain(..).collect::<Vec<_>>()).unwrap_or_default();
+    for (json, reply) in messages {
+        let name = json.get("console").and_then(|c| c.as_str()).unwrap_or_default().to_string();
+        let result = ConsoleCommand::parse(&json).and_then(|command| command.apply(ctx, gameboard));
+        match &result {
+            Ok(message) => info!(target: "galaga", "console {}: {}", name, message),
+            Err(error) => warn!(target: "galaga", "console {} failed: {}", name, error),
+        }
+        let _ = reply.send(ack(&name, &result));
+    }
+}
@@ -0,0 +1,102 @@
+use std::collections::BTreeMap;
+use std::time::{Duration, Instant};
+use pelican_ui::Context;
+use pelican_ui_std::Offset;
+use pelican_game_engine::{Sprite, Gameboard};
+
+use crate::game_collection::galaga_game::galaga::GameState;
+use crate::game_collection::galaga_game::hitbox::Hitbox;
+use crate::game_collection::galaga_game::Art;
+
+// Sprites that get a hitbox outline and id label; stars, effects and the overlay itself are left alone.
+const OUTLINED: [&str; 4] = ["player", "enemy_", "bullet_", "missile_"];
+const LINE: f32 = 1.0;
+const DIGIT: (f32, f32) = (4.0, 7.0);
+
+// Developer overlay: hitbox outlines and id numbers drawn on the board, plus timings, entity counts and
+// state flags on a text line under it. Turned on from Settings.
+#[derive(Debug, Clone, Default)]
+pub struct DebugOverlay {
+    pub enabled: bool,
+    sprites: Vec<String>,
+    last_tick: Option<Instant>,
+    fps: f32,
+    tick_time: Duration,
+    queue_depth: usize,
+    counts: BTreeMap<String, usize>,
+}
+
+impl DebugOverlay {
+    // Called as a tick starts, with the number of actions waiting in the queue.
+    pub fn tick_started(&mut self, queue_depth: usize) {
+        let now = Instant::now();
+        if let Some(last) = self.last_tick {
+            let fps = 1.0 / (now - last).as_secs_f32().max(0.0001);
+            self.fps = if self.fps == 0.0 { fps } else { self.fps * 0.9 + fps * 0.1 };
+        }
+        self.last_tick = Some(now);
+        self.queue_depth = queue_depth;
+    }
+
+    pub fn tick_finished(&mut self, started: Instant) {
+        self.tick_time = started.elapsed();
+    }
+
+    // Redraws the overlay from scratch; clears it once turned off.
+    pub fn draw(&mut self, ctx: &mut Context, gameboard: &mut Gameboard) {
+        self.sprites.drain(..).for_each(|id| gameboard.remove_sprite_by_id(&id));
+        if !self.enabled { return; }
+
+        self.counts.clear();
+        for sprite in gameboard.2.iter() {
+            let id = sprite.id();
+            let kind = id.split('_').next().unwrap_or(id);
+            *self.counts.entry(kind.to_string()).or_default() += 1;
+        }
+
+        let ids = gameboard.2.iter().map(|s| s.id().to_string()).filter(|id| OUTLINED.iter().any(|k| id.starts_with(k))).collect::<Vec<_>>();
+        for id in ids {
+            let hitbox = Hitbox::of(ctx.state().get_mut_or_default::<GameState>(), &id);
+            let Some(sprite) = gameboard.get_sprite_by_id(&id) else { continue };
+            let (pos, dim) = (sprite.position(ctx), sprite.dimensions().clone());
+            match hitbox.outline(pos, dim) {
+                (pos, dim, true) => self.add(ctx, gameboard, Art::DebugRing, pos, dim),
+                ((x, y), (w, h), false) => {
+                    self.add(ctx, gameboard, Art::DebugLine, (x, y), (w, LINE));
+                    self.add(ctx, gameboard, Art::DebugLine, (x, y + h - LINE), (w, LINE));
+                    self.add(ctx, gameboard, Art::DebugLine, (x, y), (LINE, h));
+                    self.add(ctx, gameboard, Art::DebugLine, (x + w - LINE, y), (LINE, h));
+                }
+            }
+            // The board can only draw images, so ids are labelled with their number.
+            if let Some(number) = id.rsplit('_').next().filter(|n| !n.is_empty() && n.bytes().all(|b| b.is_ascii_digit())) {
+                for (i, b) in number.bytes().enumerate() {
+                    let x = pos.0 + i as f32 * (DIGIT.0 + 1.0);
+                    self.add(ctx, gameboard, Art::Digit(b - b'0'), (x, pos.1 - DIGIT.1 - 1.0), DIGIT);
+                }
+            }
+        }
+    }
+
+    fn add(&mut self, ctx: &mut Context, gameboard: &mut Gameboard, art: Art, pos: (f32, f32), dim: (f32, f32)) {
+        let id = format!("dbg_{}", self.sprites.len());
+        let sprite = Sprite::new(ctx, &id, art.name(), dim, (Offset::Static(pos.0), Offset::Static(pos.1)));
+        gameboard.insert_sprite(ctx, sprite);
+        self.sprites.push(id);
+    }
+
+    // The text line shown under the board.
+    pub fn summary(gamestate: &GameState) -> String {
+        let debug = &gamestate.debug;
+        let yes_no = |on: bool| if on {"yes"} else {"no"};
+        let counts = debug.counts.iter().filter(|(kind, _)| kind.as_str() != "dbg").map(|(kind, n)| format!("{} {}", kind, n)).collect::<Vec<_>>().join(", ");
+        let player = gamestate.player.as_ref();
+        format!(
+            "FPS {:.0} | tick {:.1}ms | queue {} | {} | wave {} frame {} | paused {} shoot {} auto-move {} auto-shoot {} invincible {} shielded {} respawning {} frozen {}",
+            debug.fps, debug.tick_time.as_secs_f32() * 1000.0, debug.queue_depth, counts, gamestate.wave, gamestate.frame,
+            yes_no(gamestate.paused), yes_no(gamestate.can_shoot), yes_no(gamestate.player_auto_move), yes_no(gamestate.player_auto_shoot),
+            yes_no(gamestate.player_invincible), yes_no(player.is_some_and(|p| p.is_invulnerable())), yes_no(player.is_some_and(|p| p.is_respawning())),
+            yes_no(gamestate.enemies_frozen),
+        )
+    }
+}
@@ -0,0 +1,178 @@
+use std::sync::atomic::{AtomicU64, Ordering};
+use pelican_ui::Context;
+use pelican_ui_std::Offset;
+use pelican_game_engine::{Sprite, Gameboard};
+use serde::{Deserialize, Serialize};
+use rand::Rng;
+
+use crate::game_collection::galaga_game::galaga::GameState;
+use crate::game_collection::galaga_game::Art;
+
+static NEXT_ID: AtomicU64 = AtomicU64::new(1);
+
+// Game-clock frames each effect lasts.
+const SHAKE_TICKS: u64 = 12;
+const FLASH_TICKS: u64 = 4;
+const POPUP_TICKS: u64 = 40;
+const DEBRIS_TICKS: u64 = 24;
+const DEBRIS_PIECES: usize = 6;
+// Furthest the board moves at the start of a shake, in points.
+const SHAKE_STRENGTH: f32 = 6.0;
+
+// The optional feedback effects. Each can be turned off in Settings, e.g. by players sensitive to motion.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EffectKind {
+    Shake,
+    Flash,
+    Popups,
+    Debris,
+}
+
+impl EffectKind {
+    pub const ALL: [EffectKind; 4] = [EffectKind::Shake, EffectKind::Flash, EffectKind::Popups, EffectKind::Debris];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            EffectKind::Shake => "Screen Shake",
+            EffectKind::Flash => "Hit Flash",
+            EffectKind::Popups => "Score Pop-ups",
+            EffectKind::Debris => "Debris",
+        }
+    }
+
+    pub fn description(&self) -> &'static str {
+        match self {
+            EffectKind::Shake => "Shakes the board when the ship is hit.",
+            EffectKind::Flash => "Flashes enemies white when a shot lands.",
+            EffectKind::Popups => "Floats the points for each kill up from where it happened.",
+            EffectKind::Debris => "Scatters pieces from each destroyed enemy.",
+        }
+    }
+}
+
+// Which effects are on, saved with the player's profile.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EffectSettings {
+    pub shake: bool,
+    pub flash: bool,
+    pub popups: bool,
+    pub debris: bool,
+}
+
+impl Default for EffectSettings {
+    fn default() -> Self {
+        EffectSettings { shake: true, flash: true, popups: true, debris: true }
+    }
+}
+
+impl EffectSettings {
+    fn get_mut(&mut self, kind: EffectKind) -> &mut bool {
+        match kind {
+            EffectKind::Shake => &mut self.shake,
+            EffectKind::Flash => &mut self.flash,
+            EffectKind::Popups => &mut self.popups,
+            EffectKind::Debris => &mut self.debris,
+        }
+    }
+
+    pub fn enabled(&self, kind: EffectKind) -> bool {
+        match kind {
+            EffectKind::Shake => self.shake,
+            EffectKind::Flash => self.flash,
+            EffectKind::Popups => self.popups,
+            EffectKind::Debris => self.debris,
+        }
+    }
+
+    pub fn toggle(&mut self, kind: EffectKind) -> bool {
+        let on = self.get_mut(kind);
+        *on = !*on;
+        *on
+    }
+
+    pub fn label(&self, kind: EffectKind) -> String {
+        format!("{}: {}", kind.label(), if self.enabled(kind) {"On"} else {"Off"})
+    }
+}
+
+// A few short-lived sprites that drift together and are removed once the game clock reaches `until`.
+#[derive(Debug, Clone)]
+pub struct Effect {
+    ids: Vec<String>,
+    velocity: (f32, f32),
+    until: u64,
+}
+
+impl Effect {
+    fn sprite(ctx: &mut Context, gameboard: &mut Gameboard, art: Art, dim: (f32, f32), pos: (f32, f32)) -> String {
+        let id = format!("fx_{}", NEXT_ID.fetch_add(1, Ordering::Relaxed));
+        let sprite = Sprite::new(ctx, &id, art.name(), dim, (Offset::Static(pos.0), Offset::Static(pos.1)));
+        gameboard.insert_sprite(ctx, sprite);
+        id
+    }
+
+    pub fn react(&mut self, gameboard: &mut Gameboard, clock: u64) -> bool {
+        if clock >= self.until {
+            self.ids.iter().for_each(|id| gameboard.remove_sprite_by_id(id));
+            return false;
+        }
+        for id in &self.ids {
+            if let Some(sprite) = gameboard.get_sprite_by_id(id) {
+                sprite.adjustments().0 += self.velocity.0;
+                sprite.adjustments().1 += self.velocity.1;
+            }
+        }
+        true
+    }
+}
+
+// Feedback for an enemy shot down at `pos`: a white flash over it, its points floating up, and debris, each if turned on.
+pub fn enemy_destroyed(ctx: &mut Context, gameboard: &mut Gameboard, art: Option<Art>, pos: (f32, f32), dim: (f32, f32), points: u32) {
+    let gamestate = ctx.state().get_mut_or_default::<GameState>();
+    let (settings, clock) = (gamestate.effect_settings, gamestate.frame);
+    let center = (pos.0 + dim.0 / 2.0, pos.1 + dim.1 / 2.0);
+    let mut effects = Vec::new();
+
+    if let Some(flash) = art.and_then(|a| a.flash()).filter(|_| settings.flash) {
+        let ids = vec![Effect::sprite(ctx, gameboard, flash, dim, pos)];
+        effects.push(Effect { ids, velocity: (0.0, 0.0), until: clock + FLASH_TICKS });
+    }
+
+    if settings.debris {
+        let mut rng = rand::thread_rng();
+        for _ in 0..DEBRIS_PIECES {
+            let angle = rng.gen_range(0.0..std::f32::consts::TAU);
+            let speed = rng.gen_range(1.0..2.5);
+            let ids = vec![Effect::sprite(ctx, gameboard, Art::Debris, (5.0, 5.0), center)];
+            effects.push(Effect { ids, velocity: (angle.cos() * speed, angle.sin() * speed), until: clock + DEBRIS_TICKS });
+        }
+    }
+
+    if settings.popups {
+        let digits = std::iter::once(Art::DigitPlus).chain(points.to_string().bytes().map(|b| Art::Digit(b - b'0'))).collect::<Vec<_>>();
+        let (w, h, gap) = (6.0, 10.0, 2.0);
+        let width = digits.len() as f32 * (w + gap) - gap;
+        let ids = digits.into_iter().enumerate().map(|(i, digit)| {
+            let x = center.0 - width / 2.0 + i as f32 * (w + gap);
+            Effect::sprite(ctx, gameboard, digit, (w, h), (x, center.1 - h / 2.0))
+        }).collect();
+        effects.push(Effect { ids, velocity: (0.0, -0.6), until: clock + POPUP_TICKS });
+    }
+
+    ctx.state().get_mut_or_default::<GameState>().effects.extend(effects);
+}
+
+// Starts a board shake, if turned on.
+pub fn player_hit(gamestate: &mut GameState) {
+    if gamestate.effect_settings.shake {
+        gamestate.shake_until = gamestate.frame + SHAKE_TICKS;
+    }
+}
+
+// How far to nudge the whole board this frame; the shake dies down over its length.
+pub fn shake_offset(clock: u64, until: u64) -> (f32, f32) {
+    if clock >= until { return (0.0, 0.0); }
+    let strength = (until - clock) as f32 / SHAKE_TICKS as f32 * SHAKE_STRENGTH;
+    let mut rng = rand::thread_rng();
+    (rng.gen_range(-1.0..=1.0) * strength, rng.gen_range(-1.0..=1.0) * strength)
+}
@@ -1,6 +1,11 @@
 use pelican_ui::events::Event;
 use pelican_ui::Context;
 
+use crate::game_collection::shared::input::InputKind;
+use crate::game_collection::shared::keybindings::Control;
+use crate::game_collection::galaga_game::assists::AssistChange;
+use crate::game_collection::galaga_game::effects::EffectKind;
+
 #[derive(Clone, Debug)]
 pub struct AdjustPressureEvent(pub f32);
 impl Event for AdjustPressureEvent {
@@ -39,4 +44,144 @@ impl Event for ToggleInvincibility {
     fn pass(self: Box<Self>, _ctx: &mut Context, children: Vec<((f32, f32), (f32, f32))>) -> Vec<Option<Box<dyn Event>>> {
         children.into_iter().map(|_| Some(self.clone() as Box<dyn Event>)).collect()
     }
-}
\ No newline at end of file
+}
+
+#[derive(Clone, Debug)]
+pub struct CycleLogLevel;
+impl Event for CycleLogLevel {
+    fn pass(self: Box<Self>, _ctx: &mut Context, children: Vec<((f32, f32), (f32, f32))>) -> Vec<Option<Box<dyn Event>>> {
+        children.into_iter().map(|_| Some(self.clone() as Box<dyn Event>)).collect()
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct ToggleSessionLog;
+impl Event for ToggleSessionLog {
+    fn pass(self: Box<Self>, _ctx: &mut Context, children: Vec<((f32, f32), (f32, f32))>) -> Vec<Option<Box<dyn Event>>> {
+        children.into_iter().map(|_| Some(self.clone() as Box<dyn Event>)).collect()
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct ToggleInputSource(pub InputKind);
+impl Event for ToggleInputSource {
+    fn pass(self: Box<Self>, _ctx: &mut Context, children: Vec<((f32, f32), (f32, f32))>) -> Vec<Option<Box<dyn Event>>> {
+        children.into_iter().map(|_| Some(self.clone() as Box<dyn Event>)).collect()
+    }
+}
+
+#[cfg(feature = "serial")]
+#[derive(Clone, Debug)]
+pub struct CycleSerialPort;
+#[cfg(feature = "serial")]
+impl Event for CycleSerialPort {
+    fn pass(self: Box<Self>, _ctx: &mut Context, children: Vec<((f32, f32), (f32, f32))>) -> Vec<Option<Box<dyn Event>>> {
+        children.into_iter().map(|_| Some(self.clone() as Box<dyn Event>)).collect()
+    }
+}
+
+#[cfg(feature = "serial")]
+#[derive(Clone, Debug)]
+pub struct AdjustSerialBaud(pub i32);
+#[cfg(feature = "serial")]
+impl Event for AdjustSerialBaud {
+    fn pass(self: Box<Self>, _ctx: &mut Context, children: Vec<((f32, f32), (f32, f32))>) -> Vec<Option<Box<dyn Event>>> {
+        children.into_iter().map(|_| Some(self.clone() as Box<dyn Event>)).collect()
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct RebindKey(pub Control);
+impl Event for RebindKey {
+    fn pass(self: Box<Self>, _ctx: &mut Context, children: Vec<((f32, f32), (f32, f32))>) -> Vec<Option<Box<dyn Event>>> {
+        children.into_iter().map(|_| Some(self.clone() as Box<dyn Event>)).collect()
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct AdjustFireCooldown(pub i64);
+impl Event for AdjustFireCooldown {
+    fn pass(self: Box<Self>, _ctx: &mut Context, children: Vec<((f32, f32), (f32, f32))>) -> Vec<Option<Box<dyn Event>>> {
+        children.into_iter().map(|_| Some(self.clone() as Box<dyn Event>)).collect()
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct AdjustMaxBullets(pub i32);
+impl Event for AdjustMaxBullets {
+    fn pass(self: Box<Self>, _ctx: &mut Context, children: Vec<((f32, f32), (f32, f32))>) -> Vec<Option<Box<dyn Event>>> {
+        children.into_iter().map(|_| Some(self.clone() as Box<dyn Event>)).collect()
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct CycleAssistPreset;
+impl Event for CycleAssistPreset {
+    fn pass(self: Box<Self>, _ctx: &mut Context, children: Vec<((f32, f32), (f32, f32))>) -> Vec<Option<Box<dyn Event>>> {
+        children.into_iter().map(|_| Some(self.clone() as Box<dyn Event>)).collect()
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct AdjustAssist(pub AssistChange);
+impl Event for AdjustAssist {
+    fn pass(self: Box<Self>, _ctx: &mut Context, children: Vec<((f32, f32), (f32, f32))>) -> Vec<Option<Box<dyn Event>>> {
+        children.into_iter().map(|_| Some(self.clone() as Box<dyn Event>)).collect()
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct AdjustVolume(pub f32);
+impl Event for AdjustVolume {
+    fn pass(self: Box<Self>, _ctx: &mut Context, children: Vec<((f32, f32), (f32, f32))>) -> Vec<Option<Box<dyn Event>>> {
+        children.into_iter().map(|_| Some(self.clone() as Box<dyn Event>)).collect()
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct ToggleMute;
+impl Event for ToggleMute {
+    fn pass(self: Box<Self>, _ctx: &mut Context, children: Vec<((f32, f32), (f32, f32))>) -> Vec<Option<Box<dyn Event>>> {
+        children.into_iter().map(|_| Some(self.clone() as Box<dyn Event>)).collect()
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct ToggleEffect(pub EffectKind);
+impl Event for ToggleEffect {
+    fn pass(self: Box<Self>, _ctx: &mut Context, children: Vec<((f32, f32), (f32, f32))>) -> Vec<Option<Box<dyn Event>>> {
+        children.into_iter().map(|_| Some(self.clone() as Box<dyn Event>)).collect()
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct ToggleDebugOverlay;
+impl Event for ToggleDebugOverlay {
+    fn pass(self: Box<Self>, _ctx: &mut Context, children: Vec<((f32, f32), (f32, f32))>) -> Vec<Option<Box<dyn Event>>> {
+        children.into_iter().map(|_| Some(self.clone() as Box<dyn Event>)).collect()
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct NewPairingCode;
+impl Event for NewPairingCode {
+    fn pass(self: Box<Self>, _ctx: &mut Context, children: Vec<((f32, f32), (f32, f32))>) -> Vec<Option<Box<dyn Event>>> {
+        children.into_iter().map(|_| Some(self.clone() as Box<dyn Event>)).collect()
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct KickController(pub String);
+impl Event for KickController {
+    fn pass(self: Box<Self>, _ctx: &mut Context, children: Vec<((f32, f32), (f32, f32))>) -> Vec<Option<Box<dyn Event>>> {
+        children.into_iter().map(|_| Some(self.clone() as Box<dyn Event>)).collect()
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct RenameProfile(pub usize);
+impl Event for RenameProfile {
+    fn pass(self: Box<Self>, _ctx: &mut Context, children: Vec<((f32, f32), (f32, f32))>) -> Vec<Option<Box<dyn Event>>> {
+        children.into_iter().map(|_| Some(self.clone() as Box<dyn Event>)).collect()
+    }
+}
@@ -9,17 +9,32 @@ use pelican_ui_std::{Stack, Content, Header, Bumper, Page, Button, Offset, Expan
 use pelican_game_engine::{AspectRatio, Sprite, Gameboard, SpriteState, SpriteAction, CollisionEvent};
 
 
-use crate::ArduinoServer;
 use crate::game_collection::galaga_game::player::Player;
 use crate::game_collection::galaga_game::npcs::{Enemy, EnemyPatterns, Bullet, Explosion};
-use crate::game_collection::galaga_game::server::GameAction;
+use crate::game_collection::galaga_game::starfield::Starfield;
+use crate::game_collection::galaga_game::effects::{self, Effect, EffectSettings};
+use crate::game_collection::galaga_game::hitbox;
+use crate::game_collection::galaga_game::debug::DebugOverlay;
+use crate::game_collection::galaga_game::console;
+use crate::game_collection::shared::SharedState;
+use crate::game_collection::shared::server::GameAction;
+use crate::game_collection::shared::input::InputKind;
+use crate::game_collection::galaga_game::components::TouchControls;
+use crate::game_collection::galaga_game::assists::{Assists, ControlMode};
+use crate::game_collection::galaga_game::audio::{Audio, AudioSettings, Sound};
 use crate::game_collection::galaga_game::settings::Settings;
+use crate::game_collection::shared::metrics::{SessionMetrics, SharedMetrics};
+use crate::game_collection::shared::report::ReportPage;
+use crate::game_collection::shared::profiles::Profile;
+use crate::game_collection::launcher::Launcher;
 
 use std::time::Instant;
 use rand::thread_rng;
 use rand::Rng;
-use std::sync::{Arc, Mutex};
-use std::collections::VecDeque;
+use log::debug;
+
+// Sprite id prefixes that are only drawn and never collide.
+const DECORATIVE: [&str; 3] = ["star_", "fx_", "dbg_"];
 
 #[derive(Debug, Default, Clone)]
 pub struct GameState {
@@ -27,52 +42,152 @@ pub struct GameState {
     pub enemies: Vec<Enemy>,
     pub bullets: Vec<Bullet>,
     pub explosions: Vec<Explosion>,
+    pub starfield: Starfield,
+    pub effects: Vec<Effect>,
+    pub effect_settings: EffectSettings,
+    // Game-clock frame the current board shake ends on.
+    pub shake_until: u64,
+    // Enemy waves spawned this round.
+    pub wave: u32,
     pub interval: Option<Instant>,
-    pub action_queue: Option<Arc<Mutex<VecDeque<GameAction>>>>,
     pub peak_min: f64,
     pub can_shoot: bool,
     pub player_auto_move: bool,
     pub player_auto_shoot: bool,
     pub player_invincible: bool,
+    // Set from the dev console; enemies stop moving and shooting.
+    pub enemies_frozen: bool,
+    pub player_fire_cooldown: u64,
+    pub max_player_bullets: usize,
+    pub assists: Assists,
+    pub audio: Audio,
+    pub scan_index: usize,
+    pub scan_timer: Option<Instant>,
     pub score: u32,
+    pub paused: bool,
+    // Game clock: unpaused ticks since launch, which animations are timed against.
+    pub frame: u64,
+    pub debug: DebugOverlay,
+    // The shared session metrics, held here too so the board can record while it has the GameState.
+    pub metrics: Option<SharedMetrics>,
 }
 
 impl GameState {
-    pub fn new() -> Self {
-        let arduino_server = ArduinoServer::new(3030);
-        let action_queue = arduino_server.get_action_queue();
-        let _server_handle = arduino_server.start();
-        println!("Arduino WebSocket server started in background thread");
-        
+    const PEAK_MIN: f64 = 500.0;
+
+    pub fn new(metrics: Option<SharedMetrics>) -> Self {
         GameState {
             player: None,
             enemies: Vec::new(),
             bullets: Vec::new(),
             explosions: Vec::new(),
+            starfield: Starfield::default(),
+            effects: Vec::new(),
+            effect_settings: EffectSettings::default(),
+            shake_until: 0,
+            wave: 0,
             interval: Some(Instant::now()),
-            action_queue: Some(action_queue),
-            peak_min: 500.0,
+            peak_min: Self::PEAK_MIN,
             can_shoot: true,
             player_auto_move: false,
             player_auto_shoot: false,
             player_invincible: false,
-            score: 0
+            enemies_frozen: false,
+            player_fire_cooldown: 500,
+            max_player_bullets: 2,
+            assists: Assists::default(),
+            audio: Audio::default(),
+            scan_index: 1,
+            scan_timer: None,
+            score: 0,
+            paused: false,
+            frame: 0,
+            debug: DebugOverlay::default(),
+            metrics,
         }
     }
 
-    pub fn set_action_queue(&mut self, queue: Arc<Mutex<VecDeque<GameAction>>>) {
-        self.action_queue = Some(queue);
+    // Swaps in new assists; a changed control mode starts its scan over from the middle option.
+    pub fn set_assists(&mut self, assists: Assists) {
+        if assists.control_mode != self.assists.control_mode {
+            self.scan_index = 1;
+            self.scan_timer = None;
+        }
+        self.assists = assists;
+    }
+
+    // Makes sure there's a GameState to edit, picking up the active profile's settings when nothing has been played yet.
+    pub fn ensure(ctx: &mut Context) {
+        SharedState::ensure(ctx);
+        if ctx.state().get::<GameState>().is_some() { return; }
+        let shared = ctx.state().get_mut_or_default::<SharedState>().clone();
+        let mut state = GameState::new(shared.metrics);
+        if let Some(profile) = shared.profile.and_then(|name| Profile::load(&name)) {
+            state.load_profile(&profile);
+        }
+        ctx.state().set(state);
+    }
+
+    // Clears the round in progress so the next Galaga page starts on a fresh board. Settings and inputs stay.
+    pub fn reset_round(&mut self) {
+        self.player = None;
+        self.enemies.clear();
+        self.bullets.clear();
+        self.explosions.clear();
+        self.starfield = Starfield::default();
+        self.effects.clear();
+        self.shake_until = 0;
+        self.wave = 0;
+        self.enemies_frozen = false;
+        self.score = 0;
+        self.paused = false;
+    }
+
+    // Swaps in Galaga's settings from a profile, falling back to the defaults for any it doesn't have.
+    pub fn load_profile(&mut self, profile: &Profile) {
+        self.peak_min = profile.setting("peak_min").unwrap_or(Self::PEAK_MIN);
+        self.set_assists(profile.setting("assists").unwrap_or_default());
+        self.audio.load(profile.setting::<AudioSettings>("audio").unwrap_or_default());
+        self.effect_settings = profile.setting("effects").unwrap_or_default();
+    }
+
+    // Writes the current settings back to the active profile.
+    pub fn save_profile(ctx: &mut Context) {
+        let shared = ctx.state().get_mut_or_default::<SharedState>().clone();
+        let gamestate = ctx.state().get_mut_or_default::<GameState>();
+        shared.save_profile(|profile| {
+            profile.set_setting("peak_min", &gamestate.peak_min);
+            profile.set_setting("assists", &gamestate.assists);
+            profile.set_setting("audio", &gamestate.audio.settings);
+            profile.set_setting("effects", &gamestate.effect_settings);
+        });
+    }
+
+    // Adds to the current session's metrics, if they're being collected.
+    pub fn record(&self, f: impl FnOnce(&mut SessionMetrics)) {
+        if let Some(mut metrics) = self.metrics.as_ref().and_then(|m| m.lock().ok()) {
+            f(&mut metrics);
+        }
     }
 }
 
 #[derive(Debug, Component)]
-pub struct Galaga(Column, Header, ExpandableText, Option<Gameboard>);
+pub struct Galaga(Column, Header, ExpandableText, Option<Gameboard>, Option<ExpandableText>, Option<TouchControls>, Option<ExpandableText>);
 impl OnEvent for Galaga {
     fn on_event(&mut self, ctx: &mut Context, event: &mut dyn Event) -> bool {
         if let Some(TickEvent) = event.downcast_ref::<TickEvent>() {
             let mut gamestate = ctx.state().get_mut_or_default::<GameState>();
-            let score = format!("SCORE: {}", gamestate.score);
+            let score = match gamestate.paused {
+                true => format!("PAUSED - SCORE: {}", gamestate.score),
+                false => format!("SCORE: {}", gamestate.score),
+            };
             self.2.text().spans[0].text = score;
+            let scan = ControlMode::highlight(gamestate.scan_index);
+            self.4.as_mut().map(|t| t.text().spans[0].text = scan);
+            let summary = gamestate.debug.enabled.then(|| DebugOverlay::summary(gamestate));
+            if let (Some(text), Some(summary)) = (self.6.as_mut(), summary) {
+                text.text().spans[0].text = summary;
+            }
         }
         true
     }
@@ -82,7 +197,15 @@ impl AppPage for Galaga {
     fn has_nav(&self) -> bool {false}
     fn navigate(mut self: Box<Self>, ctx: &mut Context, index: usize) -> Result<Box<dyn AppPage>, Box<dyn AppPage>> {
         match index {
-            0 => Ok(Box::new(Settings::new(ctx, self.3.take().unwrap()))),
+            0 => Ok(Box::new(Settings::new(ctx, self.3.take().unwrap(), |ctx, gameboard| Box::new(Galaga::new(ctx, Some(gameboard)))))),
+            1 => Ok(Box::new(ReportPage::new(ctx, self.3.take().unwrap(), |ctx, gameboard| Box::new(Galaga::new(ctx, Some(gameboard)))))),
+            2 => {
+                GameState::save_profile(ctx);
+                let gamestate = ctx.state().get_mut_or_default::<GameState>();
+                gamestate.reset_round();
+                gamestate.audio.music(false);
+                Ok(Box::new(Launcher::new(ctx)))
+            }
             _ => Err(self)
         }
     }
@@ -90,66 +213,136 @@ impl AppPage for Galaga {
 
 impl Galaga {
     pub fn new(ctx: &mut Context, gameboard: Option<Gameboard>) -> Self {
-        let mut gameboard = gameboard.unwrap_or(Gameboard::new(ctx, AspectRatio::OneOne, Box::new(Self::on_event)));
-
-        let mut gamestate = match ctx.state().get::<GameState>() {
-            Some(state) => state.clone(),
-            None => {
-                let mut state = GameState::new();
-                let mut player = Player::new(ctx, &mut gameboard);
-        
-                player.set_auto_shoot(true);
-                player.set_auto_move(false);
-                
-                player.player_lives_display(ctx, &mut gameboard);
-                
-                state.player = Some(player);
-                state
-            }
-        };
+        let mut gameboard = gameboard.unwrap_or_else(|| Self::gameboard(ctx));
+        hitbox::preload(ctx);
+
+        GameState::ensure(ctx);
+        let mut gamestate = ctx.state().get_mut_or_default::<GameState>().clone();
+        // The profile picker sets up the state before the first game, so the player can be missing either way.
+        if gamestate.player.is_none() {
+            // Stars go in first so they're drawn behind everything else.
+            gamestate.starfield.build(ctx, &mut gameboard);
+            gamestate.starfield.set_density(ctx, &mut gameboard, Starfield::density(gamestate.wave));
+            let mut player = Player::new(ctx, &mut gameboard);
+    
+            player.set_auto_shoot(true);
+            player.set_auto_move(false);
+            
+            player.player_lives_display(ctx, &mut gameboard);
+            
+            gamestate.player = Some(player);
+        }
+        gamestate.audio.music(!gamestate.paused);
         let score = gamestate.score.to_string();
+        let switch = gamestate.assists.control_mode.is_switch().then(|| ControlMode::highlight(gamestate.scan_index));
+        let touch = ctx.state().get_mut_or_default::<SharedState>().inputs.as_ref().and_then(|i| i.lock().ok()).map(|i| i.is_active(InputKind::Touch)).unwrap_or(false);
+        let (debug, debug_summary) = (gamestate.debug.enabled, DebugOverlay::summary(&gamestate));
         ctx.state().set(gamestate);
         let settings = IconButton::navigation(ctx, "settings", |ctx: &mut Context| ctx.trigger_event(NavigateEvent(0)));
-        let header = Header::stack(ctx, None, "Galaga", Some(settings));
+        let back = IconButton::navigation(ctx, "left", |ctx: &mut Context| ctx.trigger_event(NavigateEvent(2)));
+        let header = Header::stack(ctx, Some(back), "Galaga", Some(settings));
         let text_size = ctx.theme.fonts.size.h3;
         let score = format!("SCORE: {}", score);
         let text = ExpandableText::new(ctx, &score, TextStyle::Heading, text_size, Align::Center, None);
-        Galaga(Column::center(24.0), header, text, Some(gameboard))
+        let scan = switch.map(|s| ExpandableText::new(ctx, &s, TextStyle::Heading, text_size, Align::Center, None));
+        let controls = touch.then(|| TouchControls::new(ctx));
+        let debug = debug.then(|| ExpandableText::new(ctx, &debug_summary, TextStyle::Secondary, ctx.theme.fonts.size.sm, Align::Left, None));
+        Galaga(Column::center(24.0), header, text, Some(gameboard), scan, controls, debug)
+    }
+
+    pub fn gameboard(ctx: &mut Context) -> Gameboard {
+        Gameboard::new(ctx, AspectRatio::OneOne, Box::new(Self::on_event))
     }
 
     fn on_event(gameboard: &mut Gameboard, ctx: &mut Context, event: &mut dyn Event) -> bool {
+        ctx.state().get_mut_or_default::<SharedState>().route_input(event);
+
         if let Some(TickEvent) = event.downcast_ref::<TickEvent>() {
+            let started = Instant::now();
+            let actions = ctx.state().get_mut_or_default::<SharedState>().take_actions();
             let mut gamestate = ctx.state().get_mut_or_default::<GameState>();
-            
-            if let Some(ref action_queue) = gamestate.action_queue.clone() {
-                if let Ok(mut queue) = action_queue.lock() {
-                    while let Some(action) = queue.pop_front() {
-                        match action {
-                            GameAction::MoveLeft => {
-                                if let Some(ref mut player) = gamestate.player {
-                                    player.set_state(SpriteState::MovingLeft);
-                                }
-                            }
-                            GameAction::MoveRight => {
-                                if let Some(ref mut player) = gamestate.player {
-                                    player.set_state(SpriteState::MovingRight);
-                                }
-                            }
-                            GameAction::Shoot => {
-                                if let Some(ref mut player) = gamestate.player {
-                                    player.action(SpriteAction::Shoot);
-                                }
-                            }
-                            GameAction::StopMoving => {
-                                if let Some(ref mut player) = gamestate.player {
-                                    player.set_state(SpriteState::Idle);
-                                }
-                            }
+            let mut open_settings = false;
+            gamestate.debug.tick_started(actions.len());
+
+            for action in actions {
+                // While paused only unpausing and settings get through, so nothing piles up for when play resumes.
+                if gamestate.paused && !matches!(action, GameAction::Pause | GameAction::OpenSettings) { continue; }
+                gamestate.record(|m| m.action(&action));
+                match action {
+                    GameAction::MoveLeft => {
+                        if let Some(ref mut player) = gamestate.player {
+                            player.set_state(SpriteState::MovingLeft);
+                        }
+                    }
+                    GameAction::MoveRight => {
+                        if let Some(ref mut player) = gamestate.player {
+                            player.set_state(SpriteState::MovingRight);
+                        }
+                    }
+                    GameAction::Shoot if gamestate.assists.control_mode == ControlMode::SingleSwitch => {
+                        gamestate.scan_index = (gamestate.scan_index + 1) % ControlMode::OPTIONS.len();
+                        let state = ControlMode::OPTIONS[gamestate.scan_index].0.clone();
+                        if let Some(ref mut player) = gamestate.player {
+                            player.set_state(state);
                         }
                     }
+                    GameAction::Shoot if gamestate.assists.control_mode == ControlMode::Dwell => {
+                        let state = ControlMode::OPTIONS[gamestate.scan_index].0.clone();
+                        if let Some(ref mut player) = gamestate.player {
+                            player.set_state(state);
+                        }
+                    }
+                    GameAction::Shoot => {
+                        if let Some(ref mut player) = gamestate.player {
+                            player.action(SpriteAction::Shoot);
+                        }
+                    }
+                    GameAction::StopMoving => {
+                        if let Some(ref mut player) = gamestate.player {
+                            player.set_state(SpriteState::Idle);
+                        }
+                    }
+                    GameAction::Pause => {
+                        gamestate.paused = !gamestate.paused;
+                        // The dwell highlight gets its full time again once play resumes.
+                        gamestate.scan_timer = None;
+                        let playing = !gamestate.paused;
+                        gamestate.audio.music(playing);
+                    }
+                    GameAction::OpenSettings => open_settings = true,
                 }
             }
-            
+
+            if open_settings {
+                ctx.trigger_event(NavigateEvent(0));
+                return true;
+            }
+
+            console::run_queued(ctx, gameboard);
+            let gamestate = ctx.state().get_mut_or_default::<GameState>();
+            if gamestate.paused {
+                return true;
+            }
+            gamestate.frame += 1;
+
+            if gamestate.assists.control_mode == ControlMode::Dwell {
+                match gamestate.scan_timer {
+                    Some(t) if t.elapsed() < ControlMode::DWELL => {}
+                    _ => {
+                        if gamestate.scan_timer.is_some() {
+                            gamestate.scan_index = (gamestate.scan_index + 1) % ControlMode::OPTIONS.len();
+                        }
+                        gamestate.scan_timer = Some(Instant::now());
+                    }
+                }
+            }
+
+            let mut starfield = std::mem::take(&mut gamestate.starfield);
+            let speed = gamestate.assists.game_speed;
+            starfield.scroll(ctx, gameboard, speed);
+            ctx.state().get_mut_or_default::<GameState>().starfield = starfield;
+
+            let mut gamestate = ctx.state().get_mut_or_default::<GameState>();
             let mut player = gamestate.player.clone();
     
             player.as_mut().map(|p| p.player_lives_display(ctx, gameboard));
@@ -162,6 +355,7 @@ impl Galaga {
             let mut enemies = gamestate.enemies.clone();
             
             if enemies.is_empty() {
+                if gamestate.wave > 0 { gamestate.audio.play(Sound::WaveClear); }
                 let patterns = [
                     EnemyPatterns::Star,
                     EnemyPatterns::Triangle,
@@ -170,11 +364,20 @@ impl Galaga {
                 let mut rng = thread_rng();
                 let pattern = &patterns[rng.gen_range(0..patterns.len())];
                 let new_enemies = pattern.get(ctx, gameboard).into_iter()
-                    .map(|(s, id)| Enemy::new(ctx, gameboard, s, id)).collect::<Vec<Enemy>>();
+                    .map(|(s, id, frames)| Enemy::new(ctx, gameboard, s, id, frames)).collect::<Vec<Enemy>>();
                 enemies = new_enemies;
+
+                let gamestate = ctx.state().get_mut_or_default::<GameState>();
+                gamestate.wave += 1;
+                let wave = gamestate.wave;
+                let mut starfield = std::mem::take(&mut gamestate.starfield);
+                starfield.set_density(ctx, gameboard, Starfield::density(wave));
+                ctx.state().get_mut_or_default::<GameState>().starfield = starfield;
             }
 
-            enemies.iter_mut().for_each(|e| e.react(ctx, gameboard));
+            if !ctx.state().get_mut_or_default::<GameState>().enemies_frozen {
+                enemies.iter_mut().for_each(|e| e.react(ctx, gameboard));
+            }
 
             let mut gamestate = ctx.state().get_mut_or_default::<GameState>();
             gamestate.enemies = enemies;
@@ -195,28 +398,48 @@ impl Galaga {
             let gamestate = ctx.state().get_mut_or_default::<GameState>();
             gamestate.explosions = explosions;
 
+            let clock = gamestate.frame;
+            let mut live = std::mem::take(&mut gamestate.effects);
+            live.retain_mut(|e| e.react(gameboard, clock));
+            let gamestate = ctx.state().get_mut_or_default::<GameState>();
+            gamestate.effects = live;
+            // A shake only moves where things are drawn, not where the game thinks they are.
+            let (dx, dy) = effects::shake_offset(clock, gamestate.shake_until);
+
+            let mut overlay = std::mem::take(&mut gamestate.debug);
+            overlay.draw(ctx, gameboard);
+            overlay.tick_finished(started);
+            ctx.state().get_mut_or_default::<GameState>().debug = overlay;
+
             let (maxw, maxh) = gameboard.0.size(ctx);
             gameboard.2.iter_mut().enumerate().for_each(|(i, s)| {
                 if let Some(location) = gameboard.0.0.get_mut(i+1) {
                     let (x, y) = s.position(ctx);
-                    location.0 = Offset::Static(x);
-                    location.1 = Offset::Static(y);
+                    location.0 = Offset::Static(x + dx);
+                    location.1 = Offset::Static(y + dy);
                 }
             });
 
         } else if let Some(CollisionEvent(a, b)) = event.downcast_ref::<CollisionEvent>() {
+            // Background stars, effects and the debug overlay pass through everything.
+            if [a, b].iter().any(|id| DECORATIVE.iter().any(|p| id.starts_with(p))) { return true; }
+            // Overlapping images only count once the hitboxes inside them touch.
+            if !hitbox::collides(ctx, gameboard, a, b) { return true; }
             let gamestate = &mut ctx.state().get_mut_or_default::<GameState>();
-            println!("{:?} collided into {:?}", b, a);
-            if a.starts_with("player") && b.starts_with("missile") && !gamestate.player_invincible { // enemy bullet hit player ship
+            debug!(target: "galaga", "{:?} collided into {:?}", b, a);
+            let shielded = gamestate.player_invincible || gamestate.player.as_ref().is_some_and(|p| p.is_invulnerable());
+            if a.starts_with("player") && b.starts_with("missile") && !shielded { // enemy bullet hit player ship
                 gamestate.bullets.retain_mut(|bu| bu.id() != *b);
                 gameboard.remove_sprite_by_id(b);
                 gamestate.player.as_mut().map(|p| p.action(SpriteAction::Hurt));
-            } else if a.starts_with("missile") && b.starts_with("player") && !gamestate.player_invincible { // enemy bullet hit player ship
+            } else if a.starts_with("missile") && b.starts_with("player") && !shielded { // enemy bullet hit player ship
                 gamestate.bullets.retain_mut(|bu| bu.id() != *a);
                 gameboard.remove_sprite_by_id(a);
                 gamestate.player.as_mut().map(|p| p.action(SpriteAction::Hurt));
             } else if a.starts_with("enemy") && b.starts_with("bullet") { // player bullet hit enemy ship
                 gamestate.score += 250;
+                gamestate.record(|m| m.hit());
+                gamestate.audio.play(Sound::EnemyHit);
                 gamestate.bullets.retain_mut(|bu| bu.id() != *b);
                 gameboard.remove_sprite_by_id(b);
 
@@ -225,15 +448,19 @@ impl Galaga {
                     let dim = enemy.dimensions().clone();
 
                     let gamestate = &mut ctx.state().get_mut_or_default::<GameState>();
+                    let art = gamestate.enemies.iter().find(|e| e.id() == *a).and_then(|e| e.art());
                     gamestate.enemies.retain_mut(|e| e.id() != *a);
                     gameboard.remove_sprite_by_id(a);
 
                     let explosion = Explosion::new(ctx, gameboard, pos, dim);
                     let gamestate = &mut ctx.state().get_mut_or_default::<GameState>();
                     gamestate.explosions.push(explosion);
+                    effects::enemy_destroyed(ctx, gameboard, art, pos, dim, 250);
                 }
             } else if a.starts_with("bullet") && b.starts_with("enemy") { // player bullet hit enemy ship
                 gamestate.score += 250;
+                gamestate.record(|m| m.hit());
+                gamestate.audio.play(Sound::EnemyHit);
                 gamestate.bullets.retain_mut(|bu| bu.id() != *a);
                 gameboard.remove_sprite_by_id(a);
 
@@ -242,12 +469,14 @@ impl Galaga {
                     let dim = enemy.dimensions().clone();
 
                     let gamestate = &mut ctx.state().get_mut_or_default::<GameState>();
+                    let art = gamestate.enemies.iter().find(|e| e.id() == *b).and_then(|e| e.art());
                     gamestate.enemies.retain_mut(|e| e.id() != *b);
                     gameboard.remove_sprite_by_id(b);
 
                     let explosion = Explosion::new(ctx, gameboard, pos, dim);
                     let gamestate = &mut ctx.state().get_mut_or_default::<GameState>();
                     gamestate.explosions.push(explosion);
+                    effects::enemy_destroyed(ctx, gameboard, art, pos, dim, 250);
                 }
             }else if a.starts_with("bullet") && b.starts_with("missile") || b.starts_with("bullet") && a.starts_with("missile"){ // player bullet hit enemy ship
                 let bullet = gameboard.get_sprite_by_id(b).unwrap();
@@ -264,27 +493,6 @@ impl Galaga {
                 let gamestate = &mut ctx.state().get_mut_or_default::<GameState>();
                 gamestate.explosions.push(explosion);
             }
-        } else if let Some(keyboard_event) = event.downcast_ref::<KeyboardEvent>() {
-            // Keep keyboard controls as backup/alternative input
-            let gamestate = ctx.state().get_mut_or_default::<GameState>();
-            match keyboard_event {
-                KeyboardEvent { state: KeyboardState::Pressed, key: Key::Named(NamedKey::ArrowLeft) } => {
-                    gamestate.player.as_mut().map(|p| p.set_state(SpriteState::MovingLeft));
-                }
-                KeyboardEvent { state: KeyboardState::Released, key: Key::Named(NamedKey::ArrowLeft) } => {
-                    gamestate.player.as_mut().map(|p| p.set_state(SpriteState::Idle));
-                }
-                KeyboardEvent { state: KeyboardState::Pressed, key: Key::Named(NamedKey::ArrowRight) } => {
-                    gamestate.player.as_mut().map(|p| p.set_state(SpriteState::MovingRight));
-                }
-                KeyboardEvent { state: KeyboardState::Released, key: Key::Named(NamedKey::ArrowRight) } => {
-                    gamestate.player.as_mut().map(|p| p.set_state(SpriteState::Idle));
-                }
-                KeyboardEvent { state: KeyboardState::Pressed, key: Key::Named(NamedKey::ArrowUp) } => {
-                    gamestate.player.as_mut().map(|p| p.action(SpriteAction::Shoot));
-                }
-                _ => {}
-            }
         }
         true
     }
@@ -0,0 +1,194 @@
+use std::collections::HashMap;
+use once_cell::sync::OnceCell;
+use pelican_ui::Context;
+use pelican_game_engine::Gameboard;
+use log::{debug, warn};
+
+use crate::game_collection::assets::AssetManifest;
+use crate::game_collection::galaga_game::galaga::GameState;
+use crate::game_collection::galaga_game::{Art, GalagaGame};
+
+// Cells per side of an alpha mask.
+const MASK_CELLS: usize = 24;
+// Spacing of the points sampled across two overlapping sprites.
+const SAMPLE_STEP: f32 = 2.0;
+// Illustrations whose hitbox follows their outline rather than a shape.
+const MASKED: [Art; 7] = [Art::Spaceship, Art::B2, Art::B2Flap, Art::TikiFly, Art::TikiFlyFlap, Art::Northrop, Art::NorthropFlap];
+
+// Built from the PNGs the first time Galaga starts.
+static MASKS: OnceCell<HashMap<Art, Mask>> = OnceCell::new();
+
+pub fn preload(ctx: &mut Context) {
+    MASKS.get_or_init(|| {
+        let Ok(manifest) = AssetManifest::load(&GalagaGame) else { return HashMap::new() };
+        let masks = MASKED.iter().filter_map(|art| {
+            let mask = manifest.bytes(ctx, art.name()).and_then(|bytes| Mask::from_png(&bytes));
+            if mask.is_none() { warn!(target: "galaga", "No hitbox mask for {}, using its rectangle", art.name()); }
+            mask.map(|mask| (*art, mask))
+        }).collect::<HashMap<_, _>>();
+        debug!(target: "galaga", "built {} hitbox masks", masks.len());
+        masks
+    });
+}
+
+// Which cells of an illustration are solid, from its alpha channel.
+#[derive(Debug)]
+pub struct Mask(Vec<bool>);
+
+impl Mask {
+    pub fn from_png(bytes: &[u8]) -> Option<Mask> {
+        let image = image::load_from_memory(bytes).ok()?.to_rgba8();
+        let (w, h) = image.dimensions();
+        let mut cells = vec![false; MASK_CELLS * MASK_CELLS];
+        for (x, y, _) in image.enumerate_pixels().filter(|(_, _, p)| p[3] > 127) {
+            let (cx, cy) = (x as usize * MASK_CELLS / w as usize, y as usize * MASK_CELLS / h as usize);
+            cells[cy * MASK_CELLS + cx] = true;
+        }
+        Some(Mask(cells))
+    }
+
+    // `u` and `v` run 0..1 across the image.
+    pub fn contains(&self, u: f32, v: f32) -> bool {
+        if !(0.0..1.0).contains(&u) || !(0.0..1.0).contains(&v) { return false; }
+        let (cx, cy) = ((u * MASK_CELLS as f32) as usize, (v * MASK_CELLS as f32) as usize);
+        self.0[cy * MASK_CELLS + cx]
+    }
+
+    // Box around the solid cells as (u, v) corners, or the whole image if nothing is solid.
+    pub fn bounds(&self) -> ((f32, f32), (f32, f32)) {
+        let solid = self.0.iter().enumerate().filter(|(_, solid)| **solid).map(|(i, _)| (i % MASK_CELLS, i / MASK_CELLS));
+        let (x0, y0, x1, y1) = solid.fold((MASK_CELLS, MASK_CELLS, 0, 0), |(x0, y0, x1, y1), (x, y)| (x0.min(x), y0.min(y), x1.max(x + 1), y1.max(y + 1)));
+        if x0 >= x1 { return ((0.0, 0.0), (1.0, 1.0)); }
+        let cell = 1.0 / MASK_CELLS as f32;
+        ((x0 as f32 * cell, y0 as f32 * cell), (x1 as f32 * cell, y1 as f32 * cell))
+    }
+}
+
+// Where a sprite can be hit, relative to its image rectangle.
+#[derive(Debug, Clone, Copy)]
+pub enum Hitbox {
+    // The rectangle shrunk by this fraction of its size on every side.
+    Rect(f32),
+    // A centred circle whose radius is this fraction of the shorter side.
+    Circle(f32),
+    Mask(&'static Mask),
+}
+
+impl Hitbox {
+    // Hitbox for a sprite by id. Enemies use the mask of whichever wing frame they're showing.
+    pub fn of(gamestate: &GameState, id: &str) -> Hitbox {
+        let masked = |art: Art| MASKS.get().and_then(|masks| masks.get(&art)).map(Hitbox::Mask);
+        match id {
+            "player" => masked(Art::Spaceship).unwrap_or(Hitbox::Rect(0.1)),
+            id if id.starts_with("enemy_") => gamestate.enemies.iter().find(|e| e.id() == id).and_then(|e| e.art()).and_then(masked).unwrap_or(Hitbox::Rect(0.1)),
+            id if id.starts_with("bullet_") => Hitbox::Rect(0.3),
+            id if id.starts_with("missile_") => Hitbox::Circle(0.3),
+            _ => Hitbox::Rect(0.0),
+        }
+    }
+
+    // Rectangle to outline for this hitbox as (position, size, round). Masks are outlined by the box around their solid cells.
+    pub fn outline(&self, (x, y): (f32, f32), (w, h): (f32, f32)) -> ((f32, f32), (f32, f32), bool) {
+        match self {
+            Hitbox::Rect(inset) => ((x + w * inset, y + h * inset), (w * (1.0 - 2.0 * inset), h * (1.0 - 2.0 * inset)), false),
+            Hitbox::Circle(radius) => {
+                let r = radius * w.min(h);
+                ((x + w / 2.0 - r, y + h / 2.0 - r), (2.0 * r, 2.0 * r), true)
+            }
+            Hitbox::Mask(mask) => {
+                let ((u0, v0), (u1, v1)) = mask.bounds();
+                ((x + w * u0, y + h * v0), (w * (u1 - u0), h * (v1 - v0)), false)
+            }
+        }
+    }
+
+    pub fn contains(&self, (x, y): (f32, f32), (w, h): (f32, f32), (px, py): (f32, f32)) -> bool {
+        let (u, v) = ((px - x) / w, (py - y) / h);
+        match self {
+            Hitbox::Rect(inset) => u >= *inset && u <= 1.0 - inset && v >= *inset && v <= 1.0 - inset,
+            Hitbox::Circle(radius) => {
+                let (dx, dy) = (px - (x + w / 2.0), py - (y + h / 2.0));
+                (dx * dx + dy * dy).sqrt() <= radius * w.min(h)
+            }
+            Hitbox::Mask(mask) => mask.contains(u, v),
+        }
+    }
+}
+
+// The engine reports overlapping image rectangles; this checks whether the hitboxes inside them touch.
+pub fn collides(ctx: &mut Context, gameboard: &mut Gameboard, a: &str, b: &str) -> bool {
+    let gamestate = ctx.state().get_mut_or_default::<GameState>();
+    let (hit_a, hit_b) = (Hitbox::of(gamestate, a), Hitbox::of(gamestate, b));
+    let mut rect = |id: &str| gameboard.get_sprite_by_id(id).map(|s| (s.position(ctx), s.dimensions().clone()));
+    let (Some((pos_a, dim_a)), Some((pos_b, dim_b))) = (rect(a), rect(b)) else { return false };
+    touches((hit_a, pos_a, dim_a), (hit_b, pos_b, dim_b))
+}
+
+// Samples points across the overlap of two placed hitboxes, each as (hitbox, position, size).
+fn touches((hit_a, pos_a, dim_a): (Hitbox, (f32, f32), (f32, f32)), (hit_b, pos_b, dim_b): (Hitbox, (f32, f32), (f32, f32))) -> bool {
+    let (left, top) = (pos_a.0.max(pos_b.0), pos_a.1.max(pos_b.1));
+    let (right, bottom) = ((pos_a.0 + dim_a.0).min(pos_b.0 + dim_b.0), (pos_a.1 + dim_a.1).min(pos_b.1 + dim_b.1));
+    let mut y = top + SAMPLE_STEP / 2.0;
+    while y < bottom {
+        let mut x = left + SAMPLE_STEP / 2.0;
+        while x < right {
+            if hit_a.contains(pos_a, dim_a, (x, y)) && hit_b.contains(pos_b, dim_b, (x, y)) {
+                return true;
+            }
+            x += SAMPLE_STEP;
+        }
+        y += SAMPLE_STEP;
+    }
+    false
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+    use image::{ImageFormat, Rgba, RgbaImage};
+    use super::{touches, Hitbox, Mask};
+
+    // A solid diamond touching the middle of each edge, with transparent corners, like the ship and fly art.
+    fn diamond() -> &'static Mask {
+        let image = RgbaImage::from_fn(48, 48, |x, y| {
+            let solid = (x as f32 - 23.5).abs() + (y as f32 - 23.5).abs() <= 24.0;
+            Rgba([255, 255, 255, if solid { 255 } else { 0 }])
+        });
+        let mut png = Cursor::new(Vec::new());
+        image.write_to(&mut png, ImageFormat::Png).unwrap();
+        Box::leak(Box::new(Mask::from_png(png.get_ref()).unwrap()))
+    }
+
+    #[test]
+    fn overlapping_transparent_corners_do_not_collide() {
+        let mask = Hitbox::Mask(diamond());
+        assert!(!touches((mask, (0.0, 0.0), (24.0, 24.0)), (mask, (20.0, 20.0), (24.0, 24.0))));
+    }
+
+    #[test]
+    fn overlapping_centres_collide() {
+        let mask = Hitbox::Mask(diamond());
+        assert!(touches((mask, (0.0, 0.0), (24.0, 24.0)), (mask, (4.0, 4.0), (24.0, 24.0))));
+    }
+
+    #[test]
+    fn circle_edge_is_inside_and_just_past_it_is_not() {
+        let circle = Hitbox::Circle(0.5);
+        let (pos, dim) = ((0.0, 0.0), (20.0, 20.0));
+        assert!(circle.contains(pos, dim, (20.0, 10.0)));
+        assert!(circle.contains(pos, dim, (10.0, 0.0)));
+        assert!(!circle.contains(pos, dim, (20.1, 10.0)));
+        assert!(!circle.contains(pos, dim, (10.0, -0.1)));
+    }
+
+    #[test]
+    fn rect_inset_bounds() {
+        let rect = Hitbox::Rect(0.25);
+        let (pos, dim) = ((0.0, 0.0), (40.0, 40.0));
+        assert!(rect.contains(pos, dim, (10.0, 10.0)));
+        assert!(rect.contains(pos, dim, (30.0, 30.0)));
+        assert!(!rect.contains(pos, dim, (9.9, 20.0)));
+        assert!(!rect.contains(pos, dim, (20.0, 30.1)));
+        assert_eq!(rect.outline(pos, dim), ((10.0, 10.0), (20.0, 20.0), false));
+    }
+}
@@ -2,6 +2,132 @@ pub mod galaga;
 mod components;
 pub mod player;
 mod npcs;
-pub mod server;
+mod starfield;
+mod effects;
+mod hitbox;
+mod debug;
+pub mod console;
 mod events;
-mod settings;
\ No newline at end of file
+mod settings;
+mod assists;
+pub mod audio;
+mod picker;
+
+use pelican_ui::Context;
+use pelican_ui_std::AppPage;
+
+use crate::game_collection::Game;
+
+// Illustrations Galaga's sprites are drawn with; names must match manifests/galaga.json.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Art {
+    Spaceship,
+    SpaceshipBlue,
+    SpaceshipThrust,
+    SpaceshipThrustLong,
+    SpaceshipHit,
+    SpaceshipBreakup,
+    B2,
+    B2Flap,
+    B2Flash,
+    TikiFly,
+    TikiFlyFlap,
+    TikiFlyFlash,
+    Northrop,
+    NorthropFlap,
+    NorthropFlash,
+    BulletDownward,
+    BulletBlue,
+    ExplosionSmall,
+    Explosion,
+    ExplosionFading,
+    ExplosionEmbers,
+    Star,
+    StarDim,
+    Debris,
+    Digit(u8),
+    DigitPlus,
+    DebugLine,
+    DebugRing,
+}
+
+impl Art {
+    pub const ALL: [Art; 37] = [
+        Art::Spaceship, Art::SpaceshipBlue, Art::SpaceshipThrust, Art::SpaceshipThrustLong, Art::SpaceshipHit, Art::SpaceshipBreakup,
+        Art::B2, Art::B2Flap, Art::B2Flash, Art::TikiFly, Art::TikiFlyFlap, Art::TikiFlyFlash, Art::Northrop, Art::NorthropFlap, Art::NorthropFlash,
+        Art::BulletDownward, Art::BulletBlue, Art::ExplosionSmall, Art::Explosion, Art::ExplosionFading, Art::ExplosionEmbers,
+        Art::Star, Art::StarDim, Art::Debris,
+        Art::Digit(0), Art::Digit(1), Art::Digit(2), Art::Digit(3), Art::Digit(4), Art::Digit(5), Art::Digit(6), Art::Digit(7), Art::Digit(8), Art::Digit(9),
+        Art::DigitPlus, Art::DebugLine, Art::DebugRing,
+    ];
+
+    // Animation cycles, played on game-clock frames by `npcs::Animation`.
+    pub const THRUSTER: [Art; 3] = [Art::Spaceship, Art::SpaceshipThrust, Art::SpaceshipThrustLong];
+    pub const DYING: [Art; 2] = [Art::SpaceshipHit, Art::SpaceshipBreakup];
+    pub const EXPLOSION: [Art; 4] = [Art::ExplosionSmall, Art::Explosion, Art::ExplosionFading, Art::ExplosionEmbers];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Art::Spaceship => "spaceship",
+            Art::SpaceshipBlue => "spaceship_blue",
+            Art::SpaceshipThrust => "spaceship_thrust",
+            Art::SpaceshipThrustLong => "spaceship_thrust_long",
+            Art::SpaceshipHit => "spaceship_hit",
+            Art::SpaceshipBreakup => "spaceship_breakup",
+            Art::B2 => "b2",
+            Art::B2Flap => "b2_flap",
+            Art::B2Flash => "b2_flash",
+            Art::TikiFly => "tiki_fly",
+            Art::TikiFlyFlap => "tiki_fly_flap",
+            Art::TikiFlyFlash => "tiki_fly_flash",
+            Art::Northrop => "northrop",
+            Art::NorthropFlap => "northrop_flap",
+            Art::NorthropFlash => "northrop_flash",
+            Art::BulletDownward => "bullet_downward",
+            Art::BulletBlue => "bullet_blue",
+            Art::ExplosionSmall => "explosion_small",
+            Art::Explosion => "explosion",
+            Art::ExplosionFading => "explosion_fading",
+            Art::ExplosionEmbers => "explosion_embers",
+            Art::Star => "star",
+            Art::StarDim => "star_dim",
+            Art::Debris => "debris",
+            Art::Digit(d) => ["digit_0", "digit_1", "digit_2", "digit_3", "digit_4", "digit_5", "digit_6", "digit_7", "digit_8", "digit_9"][*d as usize % 10],
+            Art::DigitPlus => "digit_plus",
+            Art::DebugLine => "debug_line",
+            Art::DebugRing => "debug_ring",
+        }
+    }
+
+    // White silhouette shown for a moment when an enemy is hit.
+    pub fn flash(&self) -> Option<Art> {
+        match self {
+            Art::B2 | Art::B2Flap => Some(Art::B2Flash),
+            Art::TikiFly | Art::TikiFlyFlap => Some(Art::TikiFlyFlash),
+            Art::Northrop | Art::NorthropFlap => Some(Art::NorthropFlash),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct GalagaGame;
+
+impl Game for GalagaGame {
+    fn name(&self) -> &'static str {"Galaga"}
+    fn description(&self) -> &'static str {"Steer the ship and shoot down the enemy flies."}
+    fn icon(&self) -> &'static str {"right"}
+
+    fn manifest(&self) -> &'static str {"galaga.json"}
+    fn illustrations(&self) -> Vec<&'static str> {Art::ALL.iter().map(|a| a.name()).collect()}
+
+    fn page(&self, ctx: &mut Context) -> Box<dyn AppPage> {
+        Box::new(picker::ProfilePicker::new(ctx))
+    }
+
+    fn settings(&self, ctx: &mut Context) -> Option<Box<dyn AppPage>> {
+        galaga::GameState::ensure(ctx);
+        let gameboard = galaga::Galaga::gameboard(ctx);
+        Some(Box::new(settings::Settings::new(ctx, gameboard, crate::game_collection::to_launcher)))
+    }
+}
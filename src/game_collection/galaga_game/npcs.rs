@@ -7,10 +7,13 @@ use pelican_ui::layout::{Area, SizeRequest, Layout};
 use pelican_ui::{Context, Component};
 use pelican_ui_std::{Stack, Content, Header, Bumper, Page, Button, Offset, TextStyle, Text, AppPage, Size, Padding, Column, Wrap, Row, ButtonSize, ButtonWidth, ButtonStyle, ButtonState, IconButton, NavigateEvent, DataItem};
 use pelican_game_engine::{AspectRatio, Sprite, Gameboard, SpriteState, SpriteAction};
-use std::time::{Duration, Instant};
+use std::time::Duration;
 use rand::Rng;
+use log::{debug, trace};
 
 use crate::game_collection::galaga_game::galaga::GameState;
+use crate::game_collection::galaga_game::Art;
+use crate::game_collection::galaga_game::audio::Sound;
 
 #[derive(Default, Debug, Clone)]
 pub struct Bullet(SpriteState, String);
@@ -20,26 +23,28 @@ impl Bullet {
         let prefix = if state == SpriteState::MovingUp {"bullet_"} else {"missile_"};
         let c = gameboard.2.iter().filter(|s| s.id().starts_with(prefix)).last().map(|s| s.id().strip_prefix(prefix).unwrap()).unwrap_or("0");
         let id = format!("{}{}", prefix, c.parse::<usize>().unwrap()+1);
-        println!("CREATED BULLET {:?}", id);
-        let image = if state == SpriteState::MovingUp {"bullet_blue"} else {"bullet_downward"};
-        let bullet = Sprite::new(ctx, &id, image, (15.0, 15.0), (Offset::Static(x), Offset::Static(y)));
+        trace!(target: "npcs", "created bullet {:?}", id);
+        let image = if state == SpriteState::MovingUp {Art::BulletBlue} else {Art::BulletDownward};
+        let bullet = Sprite::new(ctx, &id, image.name(), (15.0, 15.0), (Offset::Static(x), Offset::Static(y)));
         gameboard.insert_sprite(ctx, bullet);
         Bullet(state, id)
     }
 
     pub fn react(&mut self, ctx: &mut Context, gameboard: &mut Gameboard) -> bool {
         let max = gameboard.0.size(ctx).0;
-        // println!("Bullet is reacting with state {:?} and id {:?}", self.0, self.1);
+        let assists = &ctx.state().get_mut_or_default::<GameState>().assists;
+        let speed = 3.0 * assists.game_speed;
+        let missile_speed = speed * assists.enemy_bullet_speed;
         let bullet = gameboard.get_sprite_by_id(&self.1).unwrap();
         match self.0 {
-            SpriteState::MovingUp => bullet.adjustments().1 -= 3.0,
-            SpriteState::MovingDown => bullet.adjustments().1 += 3.0,
+            SpriteState::MovingUp => bullet.adjustments().1 -= speed,
+            SpriteState::MovingDown => bullet.adjustments().1 += missile_speed,
             _ => {}
         }
 
         let pos = bullet.position(ctx).1;
         if pos > max || pos < 0.0 {
-            println!("OUT OF BOUNDS");
+            trace!(target: "npcs", "{} out of bounds", self.1);
             gameboard.remove_sprite_by_id(&self.1);
             return false;
         }
@@ -49,33 +54,96 @@ impl Bullet {
     pub fn id(&self) -> String {self.1.clone()}
 }
 
-#[derive(Debug, Clone)]
-pub struct Explosion(String, Instant);
+// Steps a sprite through a list of frames on the game clock (`GameState::frame`), swapping its image in place.
+#[derive(Default, Debug, Clone)]
+pub struct Animation {
+    frames: &'static [Art],
+    ticks_per_frame: u64,
+    looping: bool,
+    start: u64,
+    // What the sprite was last swapped to; None until the first swap.
+    shown: Option<Art>,
+}
+
+impl Animation {
+    pub fn looping(frames: &'static [Art], ticks_per_frame: u64, start: u64) -> Self {
+        Animation { frames, ticks_per_frame, looping: true, start, shown: None }
+    }
+
+    pub fn once(frames: &'static [Art], ticks_per_frame: u64, start: u64) -> Self {
+        Animation { frames, ticks_per_frame, looping: false, start, shown: None }
+    }
 
-impl Default for Explosion {
-    fn default() -> Self {
-        Explosion(String::new(), Instant::now())
+    fn step(&self, clock: u64) -> usize {
+        (clock.saturating_sub(self.start) / self.ticks_per_frame.max(1)) as usize
+    }
+
+    // The frame due at `clock`; one-shot animations hold their last frame.
+    pub fn frame(&self, clock: u64) -> Option<Art> {
+        let step = match self.looping {
+            true => self.step(clock) % self.frames.len().max(1),
+            false => self.step(clock).min(self.frames.len().saturating_sub(1)),
+        };
+        self.frames.get(step).copied()
+    }
+
+    pub fn finished(&self, clock: u64) -> bool {
+        !self.looping && self.step(clock) >= self.frames.len()
+    }
+
+    pub fn apply(&mut self, ctx: &mut Context, gameboard: &mut Gameboard, id: &str, clock: u64) {
+        if let Some(art) = self.frame(clock) {
+            self.show(ctx, gameboard, id, art);
+        }
+    }
+
+    // Swaps the sprite's image if it isn't already showing `art`, keeping its position and size.
+    pub fn show(&mut self, ctx: &mut Context, gameboard: &mut Gameboard, id: &str, art: Art) {
+        if self.shown == Some(art) { return; }
+        let Some(sprite) = gameboard.get_sprite_by_id(id) else { return };
+        let pos = sprite.position(ctx);
+        let dim = sprite.dimensions().clone();
+        gameboard.remove_sprite_by_id(id);
+        let sprite = Sprite::new(ctx, id, art.name(), dim, (Offset::Static(pos.0), Offset::Static(pos.1)));
+        gameboard.insert_sprite(ctx, sprite);
+        self.shown = Some(art);
+    }
+
+    pub fn shown(&self) -> Option<Art> {self.shown}
+
+    // Starts over from the first frame, for a sprite that was just re-created.
+    pub fn restart(&mut self, clock: u64) {
+        self.start = clock;
+        self.shown = None;
     }
 }
 
+#[derive(Default, Debug, Clone)]
+pub struct Explosion(String, Animation);
+
 impl Explosion {
+    // Game-clock frames each explosion frame stays up for.
+    const TICKS_PER_FRAME: u64 = 4;
+
     pub fn new(ctx: &mut Context, gameboard: &mut Gameboard, pos: (f32, f32), dim: (f32, f32)) -> Self {
         let dim = (dim.0 + 10.0, dim.1 + 10.0);
         let pos = (pos.0 - 5.0, pos.1 - 5.0);
         let c = gameboard.2.iter().filter(|s| s.id().starts_with("explosion")).last().map(|s| s.id().strip_prefix("explosion_").unwrap()).unwrap_or("0");
         let id = format!("explosion_{}", c.parse::<usize>().unwrap()+1);
-        println!("CREATED EXPLOSION {:?}", id);
-        let explosion = Sprite::new(ctx, &id, "explosion", dim, (Offset::Static(pos.0), Offset::Static(pos.1)));
+        trace!(target: "npcs", "created explosion {:?}", id);
+        let explosion = Sprite::new(ctx, &id, Art::EXPLOSION[0].name(), dim, (Offset::Static(pos.0), Offset::Static(pos.1)));
         gameboard.insert_sprite(ctx, explosion);
-        Explosion(id, Instant::now())
+        let clock = ctx.state().get_mut_or_default::<GameState>().frame;
+        Explosion(id, Animation::once(&Art::EXPLOSION, Self::TICKS_PER_FRAME, clock))
     }
 
     pub fn react(&mut self, ctx: &mut Context, gameboard: &mut Gameboard) -> bool {
-        let elapsed = self.1.elapsed();
-        if elapsed.as_millis() > 200 {
+        let clock = ctx.state().get_mut_or_default::<GameState>().frame;
+        if self.1.finished(clock) {
             gameboard.remove_sprite_by_id(&self.0);
             return false
         }
+        self.1.apply(ctx, gameboard, &self.0, clock);
         true
     }
 
@@ -83,28 +151,36 @@ impl Explosion {
 }
 
 #[derive(Default, Debug, Clone)]
-pub struct Enemy(SpriteState, Vec<SpriteAction>, String, Duration);
+pub struct Enemy(SpriteState, Vec<SpriteAction>, String, Duration, Animation);
 
 impl Enemy {
-    pub fn new(ctx: &mut Context, gameboard: &mut Gameboard, sprite: Sprite, id: String) -> Self {
+    // Game-clock frames between wing flaps.
+    const FLAP_TICKS: u64 = 12;
+
+    pub fn new(ctx: &mut Context, gameboard: &mut Gameboard, sprite: Sprite, id: String, frames: &'static [Art]) -> Self {
         gameboard.insert_sprite(ctx, sprite);
         let mut rng = rand::thread_rng();
         let millis = rng.gen_range(500..=1000);
-        println!("ENEMY NEW WITH MILIS {:?}", millis);
-        Enemy(SpriteState::Idle, Vec::new(), id, Duration::from_millis(millis))
+        debug!(target: "npcs", "{} fires every {}ms", id, millis);
+        let clock = ctx.state().get_mut_or_default::<GameState>().frame;
+        Enemy(SpriteState::Idle, Vec::new(), id, Duration::from_millis(millis), Animation::looping(frames, Self::FLAP_TICKS, clock))
     }
 
     pub fn react(&mut self, ctx: &mut Context, gameboard: &mut Gameboard) {
-        let elapsed = &mut ctx.state().get_mut_or_default::<GameState>().interval.unwrap().elapsed();
-        if elapsed.as_millis() % self.3.as_millis() == 0 {
+        let gamestate = ctx.state().get_mut_or_default::<GameState>();
+        let speed = gamestate.assists.game_speed;
+        let clock = gamestate.frame;
+        let elapsed = &mut gamestate.interval.unwrap().elapsed();
+        let fire_every = ((self.3.as_millis() as f32 / speed) as u128).max(1);
+        if elapsed.as_millis() % fire_every == 0 {
             self.1.push(SpriteAction::Shoot);
         }
-        // println!("Enemy is reacting with state {:?} and actions {:?}", self.0, self.1);
+        self.4.apply(ctx, gameboard, &self.2, clock);
         let enemy = gameboard.get_sprite_by_id(&self.2).unwrap();
         match self.0 {
             SpriteState::Idle => {},
-            SpriteState::MovingLeft => enemy.adjustments().0 -= 1.0,
-            SpriteState::MovingRight => enemy.adjustments().0 += 1.0,
+            SpriteState::MovingLeft => enemy.adjustments().0 -= speed,
+            SpriteState::MovingRight => enemy.adjustments().0 += speed,
             _ => {}
         }
 
@@ -118,10 +194,18 @@ impl Enemy {
                 SpriteAction::Shoot => {
                     let gamestate = ctx.state().get_mut_or_default::<GameState>();
                     if gamestate.can_shoot {
-                        println!("ENEMY IS SHOOTING");
-                        let bullet = Bullet::new(ctx, gameboard, SpriteState::MovingDown, pos.0 + ((dim.0/2.0) - 7.5), pos.1 + 20.0);
+                        trace!(target: "npcs", "{} is shooting", self.2);
+                        let x = pos.0 + ((dim.0/2.0) - 7.5);
+                        let bullet = Bullet::new(ctx, gameboard, SpriteState::MovingDown, x, pos.1 + 20.0);
+                        // A shot falling into the player's column starts the dodge reaction timer.
+                        let aimed = gameboard.get_sprite_by_id("player").is_some_and(|p| {
+                            let px = p.position(ctx).0;
+                            x + 15.0 >= px && x <= px + p.dimensions().0
+                        });
                         let gamestate = ctx.state().get_mut_or_default::<GameState>();
                         gamestate.bullets.push(bullet);
+                        gamestate.audio.play(Sound::EnemyShoot);
+                        if aimed { gamestate.record(|m| m.threat()); }
                     }
                     false
                 },
@@ -132,6 +216,9 @@ impl Enemy {
 
     pub fn id(&self) -> String {self.2.clone()}
 
+    // The frame the enemy is drawn with right now.
+    pub fn art(&self) -> Option<Art> {self.4.shown()}
+
     pub fn set_state(&mut self, state: SpriteState) {
         self.0 = state;
     }
@@ -148,17 +235,22 @@ pub enum EnemyType {
 }
 
 impl EnemyType {
-    pub fn get(&self, ctx: &mut Context, c: usize, x: f32, y: f32) -> (Sprite, String) {
+    pub fn get(&self, ctx: &mut Context, c: usize, x: f32, y: f32) -> (Sprite, String, &'static [Art]) {
         let id = format!("enemy_{}", c);
-        let path = match self {
-            EnemyType::B2 => "b2",
-            EnemyType::TikiFly => "tiki_fly",
-            EnemyType::Northrop => "northrop",
-        };
-        (Sprite::new(ctx, &id, path, (40.0, 40.0), (Offset::Static(x), Offset::Static(y))), id)
+        let frames = self.frames();
+        (Sprite::new(ctx, &id, frames[0].name(), (40.0, 40.0), (Offset::Static(x), Offset::Static(y))), id, frames)
+    }
+
+    // Wings out, then folded in.
+    fn frames(&self) -> &'static [Art] {
+        match self {
+            EnemyType::B2 => &[Art::B2, Art::B2Flap],
+            EnemyType::TikiFly => &[Art::TikiFly, Art::TikiFlyFlap],
+            EnemyType::Northrop => &[Art::Northrop, Art::NorthropFlap],
+        }
     }
 }
-#[derive(Copy, Clone)]
+#[derive(Debug, Copy, Clone)]
 pub enum EnemyPatterns {
     Star,
     Triangle,
@@ -166,7 +258,21 @@ pub enum EnemyPatterns {
 }
 
 impl EnemyPatterns {
-    pub fn get(self, ctx: &mut Context, board: &mut Gameboard) -> Vec<(Sprite, String)> {
+    pub const ALL: [EnemyPatterns; 3] = [EnemyPatterns::Star, EnemyPatterns::Triangle, EnemyPatterns::Circle];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            EnemyPatterns::Star => "star",
+            EnemyPatterns::Triangle => "triangle",
+            EnemyPatterns::Circle => "circle",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<EnemyPatterns> {
+        Self::ALL.into_iter().find(|p| p.name() == name)
+    }
+
+    pub fn get(self, ctx: &mut Context, board: &mut Gameboard) -> Vec<(Sprite, String, &'static [Art])> {
         let (board_width, board_height) = board.0.size(ctx);
         match self {
             EnemyPatterns::Star => vec![
@@ -0,0 +1,100 @@
+use pelican_ui::events::{Event, OnEvent};
+use pelican_ui::drawable::{Drawable, Component};
+use pelican_ui::layout::{Area, SizeRequest, Layout};
+use pelican_ui::{Context, Component};
+use pelican_ui_std::{Stack, Content, Header, Page, Offset, AppPage, IconButton, NavigateEvent, DataItem, TextInput};
+
+use crate::game_collection::shared::SharedState;
+use crate::game_collection::shared::profiles::Profile;
+use crate::game_collection::shared::metrics::SessionMetrics;
+use crate::game_collection::launcher::Launcher;
+use crate::game_collection::shared::components::DataItemSettings;
+use crate::game_collection::galaga_game::galaga::{GameState, Galaga};
+use crate::game_collection::galaga_game::events::RenameProfile;
+
+// Shown before each Galaga game. Navigating to a profile's index plays as it, the index after the last one
+// creates a new profile, and the one after that goes back to the launcher. The name typed under the list is
+// used for new profiles and renames.
+#[derive(Debug, Component)]
+pub struct ProfilePicker(Stack, Page, #[skip] Vec<Profile>);
+
+impl OnEvent for ProfilePicker {
+    fn on_event(&mut self, _ctx: &mut Context, event: &mut dyn Event) -> bool {
+        if let Some(RenameProfile(i)) = event.downcast_ref::<RenameProfile>() {
+            let name = self.typed_name();
+            let Some(profile) = self.2.get_mut(*i) else { return true };
+            let label = match profile.rename(&name) {
+                Ok(()) => profile.name.clone(),
+                Err(e) => format!("{} - {}", profile.name, e),
+            };
+            *self.1.content().find_at::<DataItem>(*i).unwrap().label() = label;
+        }
+        true
+    }
+}
+
+impl AppPage for ProfilePicker {
+    fn has_nav(&self) -> bool {false}
+    fn navigate(self: Box<Self>, ctx: &mut Context, index: usize) -> Result<Box<dyn AppPage>, Box<dyn AppPage>> {
+        let profile = match self.2.get(index) {
+            Some(profile) => profile.clone(),
+            None if index == self.2.len() + 1 => return Ok(Box::new(Launcher::new(ctx))),
+            None if index == self.2.len() => {
+                let name = Some(self.typed_name()).filter(|n| !n.is_empty()).unwrap_or_else(|| Profile::next_name(&self.2));
+                if Profile::taken(&name) {
+                    *self.1.content().find_at::<DataItem>(index).unwrap().label() = format!("New Profile - {} is taken", name);
+                    return Err(self);
+                }
+                let profile = Profile::new(&name);
+                profile.save();
+                profile
+            }
+            None => return Err(self),
+        };
+        Profile::set_active(&profile.name);
+        // Coming back from the launcher keeps the running inputs and servers.
+        GameState::ensure(ctx);
+        let shared = ctx.state().get_mut_or_default::<SharedState>();
+        shared.load_profile(&profile);
+        shared.record(|m| *m = SessionMetrics::new("Galaga"));
+        ctx.state().get_mut_or_default::<GameState>().load_profile(&profile);
+        Ok(Box::new(Galaga::new(ctx, None)))
+    }
+}
+
+impl ProfilePicker {
+    pub fn new(ctx: &mut Context) -> Self {
+        let profiles = Profile::load_all();
+        let active = Profile::active();
+
+        let mut items = profiles.iter().enumerate().map(|(i, profile)| {
+            let title = match active.as_deref() == Some(profile.name.as_str()) {
+                true => format!("{} (last played)", profile.name),
+                false => profile.name.clone(),
+            };
+            let sub = format!("Best Galaga score: {} - {} sessions played.", profile.best_score("Galaga"), profile.sessions.len());
+            DataItemSettings::new(ctx, &title, &sub, vec![
+                ("add", "Play", Box::new(move |ctx: &mut Context| ctx.trigger_event(NavigateEvent(i))) as Box<dyn FnMut(&mut Context)>),
+                ("add", "Rename", Box::new(move |ctx: &mut Context| ctx.trigger_event(RenameProfile(i))) as Box<dyn FnMut(&mut Context)>),
+            ])
+        }).collect::<Vec<_>>();
+
+        let new_index = profiles.len();
+        items.push(DataItemSettings::new(ctx, "New Profile", "Starts a profile with default settings, named below or Player N.", vec![
+            ("add", "Create", Box::new(move |ctx: &mut Context| ctx.trigger_event(NavigateEvent(new_index))) as Box<dyn FnMut(&mut Context)>)
+        ]));
+
+        let back = IconButton::navigation(ctx, "left", move |ctx: &mut Context| ctx.trigger_event(NavigateEvent(new_index + 1)));
+        let header = Header::stack(ctx, Some(back), "Who's Playing?", None);
+        let mut drawable_items: Vec<Box<dyn Drawable>> = items.into_iter().map(|item| Box::new(item) as Box<dyn Drawable>).collect();
+        let name = TextInput::new(ctx, None, Some("Profile Name"), "Name for a new or renamed profile...", None, None::<(&'static str, fn(&mut Context, &mut String))>);
+        drawable_items.push(Box::new(name));
+        let content = Content::new(Offset::Start, drawable_items);
+
+        ProfilePicker(Stack::default(), Page::new(Some(header), content, None), profiles)
+    }
+
+    fn typed_name(&mut self) -> String {
+        self.1.content().find::<TextInput>().map(|input| input.value().trim().to_string()).unwrap_or_default()
+    }
+}
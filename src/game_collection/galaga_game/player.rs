@@ -1,29 +1,42 @@
 use std::any::Any;
-use std::time::Instant;
+use std::time::{Duration, Instant};
 use pelican_ui::events::{Event, Key, KeyboardEvent, KeyboardState, NamedKey, OnEvent, TickEvent};
 use pelican_ui::drawable::{Align, Drawable, Component};
 use pelican_ui::layout::{Area, SizeRequest, Layout};
 use pelican_ui::{Context, Component};
 use pelican_ui_std::{Stack, Content, Header, Bumper, Page, Button, Offset, TextStyle, Text, AppPage, Size, Padding, Column, Wrap, Row, ButtonSize, ButtonWidth, ButtonStyle, ButtonState, IconButton, NavigateEvent, DataItem};
 use pelican_game_engine::{AspectRatio, Sprite, Gameboard, SpriteState, SpriteAction};
-use crate::game_collection::galaga_game::npcs::Explosion;
+use crate::game_collection::galaga_game::npcs::{Animation, Explosion};
+use log::info;
 
 use crate::game_collection::galaga_game::npcs::Bullet;
 use crate::game_collection::galaga_game::galaga::GameState;
+use crate::game_collection::shared::SharedState;
+use crate::game_collection::galaga_game::Art;
+use crate::game_collection::galaga_game::audio::Sound;
+use crate::game_collection::galaga_game::effects;
 
 #[derive(Default, Debug, Clone)]
-// Fields: SpriteState, Vec<SpriteAction>, lives (u32), auto_shoot_timer (Option<Instant>), auto_move_enabled (bool), auto_move_direction (bool - true=right, false=left)
-pub struct Player(SpriteState, Vec<SpriteAction>, u32, Option<Instant>, bool, bool, Option<Instant>);
+// Fields: SpriteState, Vec<SpriteAction>, lives (u32), auto_shoot_timer (Option<Instant>), auto_move_enabled (bool), auto_move_direction (bool - true=right, false=left), respawn_timer (Option<Instant>), last_shot (Option<Instant>), invulnerable_until (Option<Instant>), thruster (Animation), dying (Option<Animation>)
+pub struct Player(SpriteState, Vec<SpriteAction>, u32, Option<Instant>, bool, bool, Option<Instant>, Option<Instant>, Option<Instant>, Animation, Option<Animation>);
 
 impl Player {
+    // How long each half of the respawn blink lasts.
+    const BLINK_MILLIS: u128 = 150;
+    // Game-clock frames per thruster flicker and per death animation frame.
+    const THRUST_TICKS: u64 = 3;
+    const DYING_TICKS: u64 = 8;
+
     //Sprite::new requires ctx, an id for the image, an id for the png file, the offset numbers, and the offset positions.
     pub fn new(ctx: &mut Context, gameboard: &mut Gameboard) -> Self {
-        let player = Sprite::new(ctx, "player", "spaceship", (50.0, 50.0), (Offset::Center, Offset::End));
+        let player = Sprite::new(ctx, "player", Art::Spaceship.name(), (50.0, 50.0), (Offset::Center, Offset::End));
         //gameboard is the container of everything- it has a width and height essentially
         gameboard.insert_sprite(ctx, player);
 
+        let clock = ctx.state().get_mut_or_default::<GameState>().frame;
+        let thruster = Animation::looping(&Art::THRUSTER, Self::THRUST_TICKS, clock);
         //give the state of the sprite, we construct Vec, and we give the lives.  
-        Player(SpriteState::Idle, Vec::new(), 3, None, false, false, None)
+        Player(SpriteState::Idle, Vec::new(), 3, None, false, false, None, None, None, thruster, None)
     }
 
     pub fn player_lives_display(&self, ctx: &mut Context, gameboard: &mut Gameboard) {
@@ -32,7 +45,7 @@ impl Player {
         }
         
         for i in 0..self.2 {
-            let life_sprite = Sprite::new(ctx, &format!("player_life_{}", i), "spaceship", (25.0, 25.0), 
+            let life_sprite = Sprite::new(ctx, &format!("player_life_{}", i), Art::Spaceship.name(), (25.0, 25.0), 
                 (Offset::Static((i as f32 * 5.0) + (i as f32 * 25.0)), Offset::Static(10.0)), 
             );
             gameboard.insert_sprite(ctx, life_sprite);
@@ -56,23 +69,29 @@ impl Player {
 
     
     pub fn react(&mut self, ctx: &mut Context, gameboard: &mut Gameboard) {
-        // println!("Player is reacting with state {:?} and actions {:?}", self.0, self.1);
-        
+        let clock = ctx.state().get_mut_or_default::<GameState>().frame;
         // Check if we need to respawn the player after delay
         if let Some(respawn_time) = self.6 {
-            if respawn_time.elapsed().as_secs() >= 2 {
+            let respawn = respawn_time.elapsed().as_secs() >= 2;
+            self.die(ctx, gameboard, clock, respawn);
+            if respawn {
                 // Respawn the player
-                let new_player = Sprite::new(ctx, "player", "spaceship", (50.0, 50.0), (Offset::Center, Offset::End));
+                let new_player = Sprite::new(ctx, "player", Art::Spaceship.name(), (50.0, 50.0), (Offset::Center, Offset::End));
                 gameboard.insert_sprite(ctx, new_player);
+                self.9.restart(clock);
                 self.0 = SpriteState::Idle;
                 self.6 = None;
-                println!("Player respawned after delay!");
+                let gamestate = ctx.state().get_mut_or_default::<GameState>();
+                self.8 = Some(Instant::now() + Duration::from_millis(gamestate.assists.respawn_invulnerability));
+                info!(target: "player", "Player respawned after delay");
             } else {
                 // Player is still waiting to respawn, don't process other actions
                 return;
             }
         }
         
+        self.animate(ctx, gameboard, clock);
+
         let board_width = gameboard.0.size(ctx).0;
         let player_opt = gameboard.get_sprite_by_id("player");
         
@@ -85,19 +104,20 @@ impl Player {
 
 
         let gamestate = ctx.state().get_mut_or_default::<GameState>();
+        let speed = 2.0 * gamestate.assists.game_speed;
         if gamestate.player_auto_move { 
             let player_pos = player.position(ctx).0;
             let player_width = player.dimensions().0;
             
             if self.5 { 
                 if player_pos < board_width - player_width {
-                    player.adjustments().0 += 2.0;
+                    player.adjustments().0 += speed;
                 } else {
                     self.5 = false;
                 }
             } else { 
                 if player_pos > 0.0 {
-                    player.adjustments().0 -= 2.0;
+                    player.adjustments().0 -= speed;
                 } else {
                     self.5 = true;
                 }
@@ -109,19 +129,19 @@ impl Player {
             match self.0 {
                 SpriteState::Idle => {},
                 SpriteState::MovingLeft => if player.position(ctx).0 > 0.0 {
-                    player.adjustments().0 -= 2.0;
+                    player.adjustments().0 -= speed;
                 },
                 SpriteState::MovingRight => if player.position(ctx).0 < board_width - player.dimensions().0 {
-                    player.adjustments().0 += 2.0;
+                    player.adjustments().0 += speed;
                 },
                 _ => {}
             }
         }
 
         let gamestate = ctx.state().get_mut_or_default::<GameState>();
-        if gamestate.player_auto_shoot {
+        if gamestate.player_auto_shoot || gamestate.assists.control_mode.is_switch() {
             if let Some(last_shot_time) = self.3 {
-                if last_shot_time.elapsed().as_millis() > 500 { 
+                if last_shot_time.elapsed().as_millis() >= gamestate.player_fire_cooldown as u128 { 
                     self.1.push(SpriteAction::Shoot);
                     self.3 = Some(Instant::now()); 
                 }
@@ -136,15 +156,12 @@ impl Player {
         self.1.retain_mut(|a| {
             match a {
                 SpriteAction::Hurt => {
-                    let player = gameboard.get_sprite_by_id("player").unwrap();
-                    let pos = player.position(ctx);
-                    let dim = player.dimensions().clone();
-
-                    gameboard.remove_sprite_by_id("player");
-
-                    let explosion = Explosion::new(ctx, gameboard, pos, dim);
+                    // The ship breaks up in place first; `die` swaps it for an explosion when the animation ends.
+                    self.10 = Some(Animation::once(&Art::DYING, Self::DYING_TICKS, clock));
                     let gamestate = &mut ctx.state().get_mut_or_default::<GameState>();
-                    gamestate.explosions.push(explosion);
+                    gamestate.record(|m| m.life_lost());
+                    gamestate.audio.play(Sound::PlayerHit);
+                    effects::player_hit(gamestate);
 
                     // Set respawn timer instead of immediately respawning
                     self.6 = Some(Instant::now());
@@ -152,25 +169,50 @@ impl Player {
 
                     if self.2 > 0 {
                         self.2 -= 1;
-                        println!("Player hurt! Remaining lives: {} (respawning in 2 seconds)", self.2);
+                        info!(target: "player", "Player hurt, remaining lives: {} (respawning in 2 seconds)", self.2);
                         if self.2 == 0 {
-                            println!("Player has died! (respawning in 2 seconds)");
+                            info!(target: "player", "Player has died (respawning in 2 seconds)");
                             actions_to_add.push(SpriteAction::Die);
                         }
                     }
                     false 
                 },
                 SpriteAction::Die => {
+                    let score = ctx.state().get_mut_or_default::<GameState>().score;
+                    let shared = ctx.state().get_mut_or_default::<SharedState>();
+                    shared.record(|m| m.end(score));
+                    shared.save_session();
                     let gamestate = &mut ctx.state().get_mut_or_default::<GameState>();
+                    gamestate.audio.play(Sound::GameOver);
+                    gamestate.audio.music(false);
                     gamestate.score = 0;
                     gameboard.remove_sprite_by_id("player");
                     
                     self.6 = Some(Instant::now());
                     self.2 = 3;
+                    // Game over: show the session report.
+                    ctx.trigger_event(NavigateEvent(1));
                     false
                 },
                 SpriteAction::Shoot => {
-                    let bullet = Bullet::new(ctx, gameboard, SpriteState::MovingUp, pos.0 + ((dim.0/2.0) - 7.5), pos.1 - 20.0);
+                    // Drop shots that come in faster than the cooldown or past the on-screen limit, like the arcade's two-shot rule.
+                    let gamestate = ctx.state().get_mut_or_default::<GameState>();
+                    let live = gamestate.bullets.iter().filter(|b| b.id().starts_with("bullet_")).count();
+                    let cooling = self.7.is_some_and(|t| t.elapsed().as_millis() < gamestate.player_fire_cooldown as u128);
+                    if cooling || live >= gamestate.max_player_bullets {
+                        return false;
+                    }
+                    self.7 = Some(Instant::now());
+                    gamestate.record(|m| m.shot());
+                    gamestate.audio.play(Sound::Shoot);
+                    let x = match gamestate.assists.aim_assist {
+                        true => {
+                            let enemies = gamestate.enemies.iter().map(|e| e.id()).collect::<Vec<_>>();
+                            Self::aim(ctx, gameboard, &enemies, pos.0, dim.0)
+                        }
+                        false => pos.0,
+                    };
+                    let bullet = Bullet::new(ctx, gameboard, SpriteState::MovingUp, x + ((dim.0/2.0) - 7.5), pos.1 - 20.0);
                     let gamestate = ctx.state().get_mut_or_default::<GameState>();
                     gamestate.bullets.push(bullet);
                     false
@@ -184,6 +226,10 @@ impl Player {
         }
     }
 
+    pub fn set_lives(&mut self, lives: u32) {
+        self.2 = lives;
+    }
+
     pub fn set_state(&mut self, state: SpriteState) {
         self.0 = state;
     }
@@ -195,4 +241,54 @@ impl Player {
     pub fn is_respawning(&self) -> bool {
         self.6.is_some()
     }
+
+    // A ship that's already going down can't be hit again.
+    pub fn is_invulnerable(&self) -> bool {
+        self.10.is_some() || self.8.is_some_and(|until| Instant::now() < until)
+    }
+
+    // Flickers the thruster, and alternates the ship with its blue frame while invulnerable after a respawn.
+    fn animate(&mut self, ctx: &mut Context, gameboard: &mut Gameboard, clock: u64) {
+        let blinking = match self.8 {
+            Some(until) if Instant::now() < until => (until - Instant::now()).as_millis() / Self::BLINK_MILLIS % 2 == 0,
+            _ => false,
+        };
+        let frame = if blinking { Some(Art::SpaceshipBlue) } else { self.9.frame(clock) };
+        if let Some(frame) = frame {
+            self.9.show(ctx, gameboard, "player", frame);
+        }
+    }
+
+    // Plays the death animation, then replaces the ship with an explosion. `now` cuts it short when the respawn is due.
+    fn die(&mut self, ctx: &mut Context, gameboard: &mut Gameboard, clock: u64, now: bool) {
+        let Some(dying) = self.10.as_mut() else { return };
+        if !now && !dying.finished(clock) {
+            dying.apply(ctx, gameboard, "player", clock);
+            return;
+        }
+        self.10 = None;
+        let Some(player) = gameboard.get_sprite_by_id("player") else { return };
+        let pos = player.position(ctx);
+        let dim = player.dimensions().clone();
+        gameboard.remove_sprite_by_id("player");
+
+        let explosion = Explosion::new(ctx, gameboard, pos, dim);
+        let gamestate = ctx.state().get_mut_or_default::<GameState>();
+        gamestate.explosions.push(explosion);
+    }
+
+    // Aim assist: slides the ship under the closest enemy within reach so the shot lines up with its column.
+    fn aim(ctx: &mut Context, gameboard: &mut Gameboard, enemies: &[String], x: f32, width: f32) -> f32 {
+        let center = x + width / 2.0;
+        let reach = width * 1.5;
+        let target = enemies.iter()
+            .filter_map(|id| gameboard.get_sprite_by_id(id).map(|e| e.position(ctx).0 + e.dimensions().0 / 2.0))
+            .filter(|c| (c - center).abs() <= reach)
+            .min_by(|a, b| (a - center).abs().total_cmp(&(b - center).abs()));
+        let Some(target) = target else { return x };
+        if let Some(player) = gameboard.get_sprite_by_id("player") {
+            player.adjustments().0 += target - center;
+        }
+        x + target - center
+    }
 }
\ No newline at end of file
@@ -10,19 +10,100 @@ use pelican_game_engine::{AspectRatio, Sprite, Gameboard, SpriteState, SpriteAct
 use std::sync::{Arc, Mutex};
 use std::collections::VecDeque;
 
-use crate::game_collection::galaga_game::events::{AdjustPressureEvent, ToggleFliesShoot, ToggleAutoMove, ToggleAutoShoot, ToggleInvincibility};
-use crate::game_collection::galaga_game::galaga::{GameState, Galaga};
+use crate::game_collection::galaga_game::events::{AdjustPressureEvent, ToggleFliesShoot, ToggleAutoMove, ToggleAutoShoot, ToggleInvincibility, CycleLogLevel, ToggleSessionLog, ToggleInputSource, RebindKey, AdjustFireCooldown, AdjustMaxBullets, CycleAssistPreset, AdjustAssist, AdjustVolume, ToggleMute, ToggleEffect, ToggleDebugOverlay, NewPairingCode, KickController};
+use crate::game_collection::galaga_game::effects::EffectKind;
+use crate::game_collection::galaga_game::assists::{Assists, AssistChange};
+use crate::game_collection::shared::keybindings::{Control, KeyBindings};
+use crate::game_collection::shared::components::DataItemSettings;
+#[cfg(feature = "serial")]
+use crate::game_collection::galaga_game::events::{CycleSerialPort, AdjustSerialBaud};
+#[cfg(feature = "serial")]
+use crate::game_collection::shared::serial::{SerialConfig, BAUD_RATES};
+use crate::logger;
+use log::{debug, warn};
+use crate::game_collection::galaga_game::galaga::GameState;
+use crate::game_collection::shared::SharedState;
+use crate::game_collection::ReturnTo;
 
+// Position of the volume item; the mute toggle follows it.
+const AUDIO_INDEX: usize = 9;
+// Position of the first effect toggle; one DataItem per EffectKind follows it.
+const EFFECTS_INDEX: usize = AUDIO_INDEX + 2;
+// Position of the debug overlay toggle.
+const DEBUG_INDEX: usize = EFFECTS_INDEX + EffectKind::ALL.len();
+// Position of the assist preset; the five individual assists follow it.
+const ASSISTS_INDEX: usize = DEBUG_INDEX + 1;
+// Position of the first key binding; one DataItem per Control follows it.
+const KEYS_INDEX: usize = ASSISTS_INDEX + 6;
+// Position of the first input source toggle; one DataItem per registered source follows it,
+// then the serial port and baud rate items when serial input is compiled in.
+const INPUTS_INDEX: usize = KEYS_INDEX + Control::ALL.len();
+
+// The last field is the generation of the pairing code on screen, so a code replaced by the server is picked up.
 #[derive(Debug, Component)]
-pub struct Settings(Stack, Page, #[skip] Option<Gameboard>);
+pub struct Settings(Stack, Page, #[skip] Option<Gameboard>, #[skip] Option<Control>, #[skip] Vec<String>, #[skip] ReturnTo, #[skip] u64);
 
 impl OnEvent for Settings {
     fn on_event(&mut self, ctx: &mut Context, event: &mut dyn Event) -> bool {
+        if event.downcast_ref::<TickEvent>().is_some() {
+            // The code changes after every pairing and after repeated wrong guesses, not just from this page.
+            let shared = ctx.state().get_mut_or_default::<SharedState>();
+            let index = pairing_index(shared);
+            let Some(pairing) = shared.pairing.as_ref().and_then(|p| p.lock().ok()) else { return true };
+            if pairing.generation() != self.6 {
+                self.6 = pairing.generation();
+                *self.1.content().find_at::<DataItem>(index).unwrap().label() = format!("Pairing Code: {}", pairing.code());
+            }
+            return true;
+        }
+
+        #[cfg(feature = "serial")]
+        if event.downcast_ref::<CycleSerialPort>().is_some() {
+            self.adjust_serial(ctx, |config| {
+                let paths = SerialConfig::available_paths();
+                let next = paths.iter().position(|p| *p == config.path).map(|i| (i + 1) % paths.len());
+                if let Some(path) = paths.get(next.unwrap_or(0)) {
+                    config.path = path.clone();
+                }
+            });
+        } else if let Some(AdjustSerialBaud(step)) = event.downcast_ref::<AdjustSerialBaud>() {
+            self.adjust_serial(ctx, |config| {
+                let index = BAUD_RATES.iter().position(|b| *b == config.baud).unwrap_or(0) as i32;
+                config.baud = BAUD_RATES[(index + step).clamp(0, BAUD_RATES.len() as i32 - 1) as usize];
+            });
+        }
+
+        if let Some(KeyboardEvent { state: KeyboardState::Pressed, key }) = event.downcast_ref::<KeyboardEvent>() {
+            let bindings = ctx.state().get_mut_or_default::<SharedState>().key_bindings.clone();
+            match (self.3.take(), bindings) {
+                (Some(control), Some(bindings)) => {
+                    let Ok(mut bindings) = bindings.lock() else { return true };
+                    if !bindings.rebind(control, key.clone()) {
+                        // Keep listening so the player can pick a key that can be saved.
+                        self.3 = Some(control);
+                        let index = KEYS_INDEX + Control::ALL.iter().position(|c| *c == control).unwrap_or(0);
+                        *self.1.content().find_at::<DataItem>(index).unwrap().label() = format!("{} Key: {} can't be bound, press another key...", control.label(), KeyBindings::key_label(key));
+                        return true;
+                    }
+                    for (i, c) in Control::ALL.iter().enumerate() {
+                        *self.1.content().find_at::<DataItem>(KEYS_INDEX + i).unwrap().label() = format!("{} Key: {}", c.label(), bindings.describe(*c));
+                    }
+                }
+                (None, Some(bindings)) => {
+                    if bindings.lock().ok().and_then(|b| b.control(key)) == Some(Control::Settings) {
+                        ctx.trigger_event(NavigateEvent(0));
+                    }
+                }
+                _ => {}
+            }
+            return true;
+        }
+
         if let Some(AdjustPressureEvent(p)) = event.downcast_ref::<AdjustPressureEvent>() {
             let gamestate = &mut ctx.state().get_mut_or_default::<GameState>();
             if gamestate.peak_min < 1000.0 {
                 gamestate.peak_min += *p as f64; 
-                println!("peak: {}", gamestate.peak_min);
+                debug!(target: "galaga", "peak: {}", gamestate.peak_min);
                 *self.1.content().find_at::<DataItem>(0).unwrap().label() = format!("Touchpad Pressure: {:.0}", gamestate.peak_min);
             }
         } else if event.downcast_ref::<ToggleFliesShoot>().is_some() {
@@ -44,8 +125,7 @@ impl OnEvent for Settings {
         } else if event.downcast_ref::<ToggleAutoShoot>().is_some() {
             let gamestate = &mut ctx.state().get_mut_or_default::<GameState>();
             gamestate.player_auto_shoot = !gamestate.player_auto_shoot;
-            let val = if gamestate.player_auto_shoot {"Yes"} else {"No"};
-            *self.1.content().find_at::<DataItem>(3).unwrap().label() = format!("Player Auto Shoots: {}", val);
+            *self.1.content().find_at::<DataItem>(3).unwrap().label() = auto_shoot_label(gamestate);
             let buttons = &mut self.1.content().find_at::<DataItem>(3).unwrap().buttons();
             let label = &mut buttons.as_mut().unwrap()[0].label().as_mut().unwrap().text().spans[0].text;
             *label = if gamestate.player_auto_shoot { "Turn Off".to_string() } else { "Turn On".to_string() };
@@ -57,6 +137,112 @@ impl OnEvent for Settings {
             let buttons = &mut self.1.content().find_at::<DataItem>(4).unwrap().buttons();
             let label = &mut buttons.as_mut().unwrap()[0].label().as_mut().unwrap().text().spans[0].text;
             *label = if gamestate.player_invincible { "Turn Off".to_string() } else { "Turn On".to_string() };
+        } else if event.downcast_ref::<CycleLogLevel>().is_some() {
+            let level = logger::cycle_level();
+            *self.1.content().find_at::<DataItem>(5).unwrap().label() = format!("Log Level: {}", level);
+        } else if event.downcast_ref::<ToggleSessionLog>().is_some() {
+            let started = match logger::session_file() {
+                Some(_) => Ok(None),
+                None => logger::start_session_file().map(Some),
+            };
+            let path = started.as_ref().ok().cloned().flatten();
+            if path.is_none() { logger::stop_session_file(); }
+            let sub = match &started {
+                Ok(_) => session_log_sub(),
+                Err(e) => {
+                    warn!(target: "galaga", "Could not start the session log: {}", e);
+                    format!("Could not start the session log: {}", e)
+                }
+            };
+            let val = if path.is_some() {"On"} else {"Off"};
+            *self.1.content().find_at::<DataItem>(6).unwrap().label() = format!("Session Log File: {}", val);
+            if let Some(text) = self.1.content().find_at::<DataItem>(6).unwrap().text() { *text = sub; }
+            let buttons = &mut self.1.content().find_at::<DataItem>(6).unwrap().buttons();
+            let label = &mut buttons.as_mut().unwrap()[0].label().as_mut().unwrap().text().spans[0].text;
+            *label = if path.is_some() { "Turn Off".to_string() } else { "Turn On".to_string() };
+        } else if let Some(AdjustFireCooldown(ms)) = event.downcast_ref::<AdjustFireCooldown>() {
+            let gamestate = &mut ctx.state().get_mut_or_default::<GameState>();
+            gamestate.player_fire_cooldown = (gamestate.player_fire_cooldown as i64 + ms).clamp(100, 2000) as u64;
+            *self.1.content().find_at::<DataItem>(3).unwrap().label() = auto_shoot_label(gamestate);
+            *self.1.content().find_at::<DataItem>(7).unwrap().label() = format!("Player Fire Cooldown: {}ms", gamestate.player_fire_cooldown);
+        } else if let Some(AdjustMaxBullets(n)) = event.downcast_ref::<AdjustMaxBullets>() {
+            let gamestate = &mut ctx.state().get_mut_or_default::<GameState>();
+            gamestate.max_player_bullets = (gamestate.max_player_bullets as i32 + n).clamp(1, 10) as usize;
+            *self.1.content().find_at::<DataItem>(8).unwrap().label() = format!("Max Player Bullets: {}", gamestate.max_player_bullets);
+        } else if let Some(AdjustVolume(d)) = event.downcast_ref::<AdjustVolume>() {
+            let gamestate = &mut ctx.state().get_mut_or_default::<GameState>();
+            let volume = gamestate.audio.settings.volume + d;
+            gamestate.audio.set_volume(volume);
+            *self.1.content().find_at::<DataItem>(AUDIO_INDEX).unwrap().label() = gamestate.audio.settings.volume_label();
+        } else if event.downcast_ref::<ToggleMute>().is_some() {
+            let gamestate = &mut ctx.state().get_mut_or_default::<GameState>();
+            gamestate.audio.toggle_mute();
+            let settings = gamestate.audio.settings;
+            *self.1.content().find_at::<DataItem>(AUDIO_INDEX + 1).unwrap().label() = settings.mute_label();
+            let buttons = &mut self.1.content().find_at::<DataItem>(AUDIO_INDEX + 1).unwrap().buttons();
+            let label = &mut buttons.as_mut().unwrap()[0].label().as_mut().unwrap().text().spans[0].text;
+            *label = if settings.muted { "Unmute".to_string() } else { "Mute".to_string() };
+        } else if let Some(ToggleEffect(kind)) = event.downcast_ref::<ToggleEffect>() {
+            let gamestate = &mut ctx.state().get_mut_or_default::<GameState>();
+            let on = gamestate.effect_settings.toggle(*kind);
+            let index = EFFECTS_INDEX + EffectKind::ALL.iter().position(|k| k == kind).unwrap_or(0);
+            *self.1.content().find_at::<DataItem>(index).unwrap().label() = gamestate.effect_settings.label(*kind);
+            let buttons = &mut self.1.content().find_at::<DataItem>(index).unwrap().buttons();
+            let label = &mut buttons.as_mut().unwrap()[0].label().as_mut().unwrap().text().spans[0].text;
+            *label = if on { "Turn Off".to_string() } else { "Turn On".to_string() };
+        } else if event.downcast_ref::<ToggleDebugOverlay>().is_some() {
+            let gamestate = &mut ctx.state().get_mut_or_default::<GameState>();
+            gamestate.debug.enabled = !gamestate.debug.enabled;
+            let on = gamestate.debug.enabled;
+            debug!(target: "galaga", "debug overlay {}", if on {"on"} else {"off"});
+            *self.1.content().find_at::<DataItem>(DEBUG_INDEX).unwrap().label() = format!("Debug Overlay: {}", if on {"On"} else {"Off"});
+            let buttons = &mut self.1.content().find_at::<DataItem>(DEBUG_INDEX).unwrap().buttons();
+            let label = &mut buttons.as_mut().unwrap()[0].label().as_mut().unwrap().text().spans[0].text;
+            *label = if on { "Turn Off".to_string() } else { "Turn On".to_string() };
+        } else if event.downcast_ref::<CycleAssistPreset>().is_some() {
+            let gamestate = &mut ctx.state().get_mut_or_default::<GameState>();
+            let preset = gamestate.assists.current_preset().next();
+            gamestate.set_assists(Assists::preset(preset));
+            let assists = gamestate.assists.clone();
+            self.refresh_assists(&assists);
+        } else if let Some(AdjustAssist(change)) = event.downcast_ref::<AdjustAssist>() {
+            let gamestate = &mut ctx.state().get_mut_or_default::<GameState>();
+            let mut assists = gamestate.assists.clone();
+            assists.apply(*change);
+            gamestate.set_assists(assists.clone());
+            self.refresh_assists(&assists);
+        } else if let Some(RebindKey(control)) = event.downcast_ref::<RebindKey>() {
+            self.3 = Some(*control);
+            let index = KEYS_INDEX + Control::ALL.iter().position(|c| c == control).unwrap_or(0);
+            *self.1.content().find_at::<DataItem>(index).unwrap().label() = format!("{} Key: press a key...", control.label());
+        } else if let Some(ToggleInputSource(kind)) = event.downcast_ref::<ToggleInputSource>() {
+            let Some(inputs) = ctx.state().get_mut_or_default::<SharedState>().inputs.clone() else { return true };
+            let Ok(mut inputs) = inputs.lock() else { return true };
+            let index = INPUTS_INDEX + inputs.kinds().iter().position(|k| k == kind).unwrap_or(0);
+            let active = inputs.toggle(*kind);
+            let val = if active {"On"} else {"Off"};
+            *self.1.content().find_at::<DataItem>(index).unwrap().label() = format!("{} Input: {}", kind.label(), val);
+            let buttons = &mut self.1.content().find_at::<DataItem>(index).unwrap().buttons();
+            let label = &mut buttons.as_mut().unwrap()[0].label().as_mut().unwrap().text().spans[0].text;
+            *label = if active { "Turn Off".to_string() } else { "Turn On".to_string() };
+        } else if event.downcast_ref::<NewPairingCode>().is_some() {
+            let shared = ctx.state().get_mut_or_default::<SharedState>();
+            let index = pairing_index(shared);
+            let Some(mut pairing) = shared.pairing.as_ref().and_then(|p| p.lock().ok()) else { return true };
+            pairing.new_code();
+            self.6 = pairing.generation();
+            *self.1.content().find_at::<DataItem>(index).unwrap().label() = format!("Pairing Code: {}", pairing.code());
+        } else if let Some(KickController(device)) = event.downcast_ref::<KickController>() {
+            let shared = ctx.state().get_mut_or_default::<SharedState>();
+            let Some(position) = self.4.iter().position(|d| d == device) else { return true };
+            let index = pairing_index(shared) + 1 + position;
+            if let Some(mut pairing) = shared.pairing.as_ref().and_then(|p| p.lock().ok()) {
+                pairing.kick(device);
+            }
+            *self.1.content().find_at::<DataItem>(index).unwrap().label() = format!("Controller {}: Removed", device);
+            let buttons = &mut self.1.content().find_at::<DataItem>(index).unwrap().buttons();
+            let label = &mut buttons.as_mut().unwrap()[0].label().as_mut().unwrap().text().spans[0].text;
+            *label = "Removed".to_string();
         }
         true
     }
@@ -66,20 +252,46 @@ impl AppPage for Settings {
     fn has_nav(&self) -> bool {false}
     fn navigate(mut self: Box<Self>, ctx: &mut Context, index: usize) -> Result<Box<dyn AppPage>, Box<dyn AppPage>> {
         match index {
-            0 => Ok(Box::new(Galaga::new(ctx, Some(self.2.take().unwrap())))),
+            0 => {
+                GameState::save_profile(ctx);
+                Ok((self.5)(ctx, self.2.take().unwrap()))
+            }
             _ => Err(self)
         }
     }
 }
 
 impl Settings {
-    pub fn new(ctx: &mut Context, gameboard: Gameboard) -> Self {
+    fn refresh_assists(&mut self, assists: &Assists) {
+        for (i, (label, toggle)) in assist_labels(assists).into_iter().enumerate() {
+            let item = self.1.content().find_at::<DataItem>(ASSISTS_INDEX + i).unwrap();
+            *item.label() = label;
+            if let Some(toggle) = toggle {
+                let buttons = &mut item.buttons();
+                let label = &mut buttons.as_mut().unwrap()[0].label().as_mut().unwrap().text().spans[0].text;
+                *label = toggle.to_string();
+            }
+        }
+    }
+
+    #[cfg(feature = "serial")]
+    fn adjust_serial(&mut self, ctx: &mut Context, adjust: impl FnOnce(&mut SerialConfig)) {
+        let shared = ctx.state().get_mut_or_default::<SharedState>();
+        let index = INPUTS_INDEX + shared.inputs.as_ref().and_then(|i| i.lock().ok()).map(|i| i.kinds().len()).unwrap_or(0);
+        let Some(config) = shared.serial_config.clone() else { return };
+        let Ok(mut config) = config.lock() else { return };
+        adjust(&mut config);
+        *self.1.content().find_at::<DataItem>(index).unwrap().label() = format!("Serial Port: {}", config.path);
+        *self.1.content().find_at::<DataItem>(index + 1).unwrap().label() = format!("Serial Baud Rate: {}", config.baud);
+    }
+
+    pub fn new(ctx: &mut Context, gameboard: Gameboard, back: ReturnTo) -> Self {
         let (pressure, can_shoot, auto_move, auto_shoot, invincible, can_shoot_toggle, auto_move_toggle, auto_shoot_toggle, invincible_toggle) = {
             let gamestate = ctx.state().get_mut_or_default::<GameState>();
             let pressure = format!("Touchpad Pressure: {:.0}", gamestate.peak_min);
             let can_shoot = format!("Enemy Flies Can Shoot: {}", if gamestate.can_shoot {"Yes"} else {"No"});
             let auto_move = format!("Player Auto Moves: {}", if gamestate.player_auto_move {"Yes"} else {"No"});
-            let auto_shoot = format!("Player Auto Shoots: {}", if gamestate.player_auto_shoot {"Yes"} else {"No"});
+            let auto_shoot = auto_shoot_label(gamestate);
             let invincible = format!("Player Is Invincible: {}", if gamestate.player_invincible {"Yes"} else {"No"});
             
             let can_shoot_toggle = if gamestate.can_shoot { "Turn Off" } else { "Turn On" };
@@ -90,7 +302,36 @@ impl Settings {
             (pressure, can_shoot, auto_move, auto_shoot, invincible, can_shoot_toggle, auto_move_toggle, auto_shoot_toggle, invincible_toggle)
         };
 
-        let buttons = vec![
+        let inputs = {
+            let shared = ctx.state().get_mut_or_default::<SharedState>();
+            shared.inputs.as_ref().and_then(|i| i.lock().ok()).map(|i| {
+                i.kinds().into_iter().map(|k| (k, i.is_active(k))).collect::<Vec<_>>()
+            }).unwrap_or_default()
+        };
+
+        let (cooldown, max_bullets) = {
+            let gamestate = ctx.state().get_mut_or_default::<GameState>();
+            (format!("Player Fire Cooldown: {}ms", gamestate.player_fire_cooldown), format!("Max Player Bullets: {}", gamestate.max_player_bullets))
+        };
+
+        let audio = ctx.state().get_mut_or_default::<GameState>().audio.settings;
+        let effect_settings = ctx.state().get_mut_or_default::<GameState>().effect_settings;
+        let debug_on = ctx.state().get_mut_or_default::<GameState>().debug.enabled;
+
+        let assists = assist_labels(&ctx.state().get_mut_or_default::<GameState>().assists.clone());
+
+        let keys = {
+            let shared = ctx.state().get_mut_or_default::<SharedState>();
+            let bindings = shared.key_bindings.as_ref().and_then(|b| b.lock().ok()).map(|b| b.clone()).unwrap_or_default();
+            Control::ALL.iter().map(|c| (*c, format!("{} Key: {}", c.label(), bindings.describe(*c)))).collect::<Vec<_>>()
+        };
+
+        let log_level = format!("Log Level: {}", logger::level());
+        let session_log = format!("Session Log File: {}", if logger::session_file().is_some() {"On"} else {"Off"});
+        let session_log_toggle = if logger::session_file().is_some() { "Turn Off" } else { "Turn On" };
+        let session_log_sub = session_log_sub();
+
+        let mut buttons = vec![
             DataItemSettings::new(ctx, &pressure, "Increase or decrease pressure required to perform an action.", vec![
                 ("add", "Decrease", Box::new(|ctx: &mut Context| ctx.trigger_event(AdjustPressureEvent(-50.0))) as Box<dyn FnMut(&mut Context)>),
                 ("add", "Increase", Box::new(|ctx: &mut Context| ctx.trigger_event(AdjustPressureEvent(50.0))) as Box<dyn FnMut(&mut Context)>),
@@ -101,30 +342,155 @@ impl Settings {
             DataItemSettings::new(ctx, &auto_move, "Allows player to move back and forth automatically.", vec![
                 ("add", auto_move_toggle, Box::new(|ctx: &mut Context| ctx.trigger_event(ToggleAutoMove)) as Box<dyn FnMut(&mut Context)>)
             ]),
-            DataItemSettings::new(ctx, &auto_shoot, "Allows player to automatically shoot every 200 millis.", vec![
+            DataItemSettings::new(ctx, &auto_shoot, "Allows player to automatically shoot as often as the fire cooldown allows.", vec![
                 ("add", auto_shoot_toggle, Box::new(|ctx: &mut Context| ctx.trigger_event(ToggleAutoShoot)) as Box<dyn FnMut(&mut Context)>)
             ]),
             DataItemSettings::new(ctx, &invincible, "Allows player to be invincible to enemy fire.", vec![
                 ("add", invincible_toggle, Box::new(|ctx: &mut Context| ctx.trigger_event(ToggleInvincibility)) as Box<dyn FnMut(&mut Context)>)
             ]),
+            DataItemSettings::new(ctx, &log_level, "How much detail is written to the console and session log.", vec![
+                ("add", "Change", Box::new(|ctx: &mut Context| ctx.trigger_event(CycleLogLevel)) as Box<dyn FnMut(&mut Context)>)
+            ]),
+            DataItemSettings::new(ctx, &session_log, &session_log_sub, vec![
+                ("add", session_log_toggle, Box::new(|ctx: &mut Context| ctx.trigger_event(ToggleSessionLog)) as Box<dyn FnMut(&mut Context)>)
+            ]),
+            DataItemSettings::new(ctx, &cooldown, "Shortest time between two player shots.", vec![
+                ("add", "Decrease", Box::new(|ctx: &mut Context| ctx.trigger_event(AdjustFireCooldown(-50))) as Box<dyn FnMut(&mut Context)>),
+                ("add", "Increase", Box::new(|ctx: &mut Context| ctx.trigger_event(AdjustFireCooldown(50))) as Box<dyn FnMut(&mut Context)>),
+            ]),
+            DataItemSettings::new(ctx, &max_bullets, "How many player bullets can be on screen at once.", vec![
+                ("add", "Decrease", Box::new(|ctx: &mut Context| ctx.trigger_event(AdjustMaxBullets(-1))) as Box<dyn FnMut(&mut Context)>),
+                ("add", "Increase", Box::new(|ctx: &mut Context| ctx.trigger_event(AdjustMaxBullets(1))) as Box<dyn FnMut(&mut Context)>),
+            ]),
+            DataItemSettings::new(ctx, &audio.volume_label(), "How loud sound effects and music play.", vec![
+                ("add", "Decrease", Box::new(|ctx: &mut Context| ctx.trigger_event(AdjustVolume(-0.1))) as Box<dyn FnMut(&mut Context)>),
+                ("add", "Increase", Box::new(|ctx: &mut Context| ctx.trigger_event(AdjustVolume(0.1))) as Box<dyn FnMut(&mut Context)>),
+            ]),
+            DataItemSettings::new(ctx, &audio.mute_label(), "Silences all sound without changing the volume.", vec![
+                ("add", if audio.muted {"Unmute"} else {"Mute"}, Box::new(|ctx: &mut Context| ctx.trigger_event(ToggleMute)) as Box<dyn FnMut(&mut Context)>)
+            ]),
+        ];
+
+        buttons.extend(EffectKind::ALL.into_iter().map(|kind| {
+            let toggle = if effect_settings.enabled(kind) { "Turn Off" } else { "Turn On" };
+            DataItemSettings::new(ctx, &effect_settings.label(kind), kind.description(), vec![
+                ("add", toggle, Box::new(move |ctx: &mut Context| ctx.trigger_event(ToggleEffect(kind))) as Box<dyn FnMut(&mut Context)>)
+            ])
+        }));
+
+        let debug_overlay = format!("Debug Overlay: {}", if debug_on {"On"} else {"Off"});
+        buttons.push(DataItemSettings::new(ctx, &debug_overlay, "Draws hitboxes, sprite ids and timings over the board.", vec![
+            ("add", if debug_on {"Turn Off"} else {"Turn On"}, Box::new(|ctx: &mut Context| ctx.trigger_event(ToggleDebugOverlay)) as Box<dyn FnMut(&mut Context)>)
+        ]));
+
+        let assist_items: [(&str, Vec<(&'static str, &str, Box<dyn FnMut(&mut Context)>)>); 6] = [
+            ("Presets set every assist below at once.", vec![
+                ("add", "Change", Box::new(|ctx: &mut Context| ctx.trigger_event(CycleAssistPreset)) as Box<dyn FnMut(&mut Context)>),
+            ]),
+            ("How fast enemy missiles fall.", vec![
+                ("add", "Slower", Box::new(|ctx: &mut Context| ctx.trigger_event(AdjustAssist(AssistChange::EnemyBulletSpeed(-0.25)))) as Box<dyn FnMut(&mut Context)>),
+                ("add", "Faster", Box::new(|ctx: &mut Context| ctx.trigger_event(AdjustAssist(AssistChange::EnemyBulletSpeed(0.25)))) as Box<dyn FnMut(&mut Context)>),
+            ]),
+            ("Lines each shot up with the closest enemy column.", vec![
+                ("add", assists[2].1.unwrap_or_default(), Box::new(|ctx: &mut Context| ctx.trigger_event(AdjustAssist(AssistChange::AimAssist))) as Box<dyn FnMut(&mut Context)>),
+            ]),
+            ("Single Switch: each press steps through left, stop and right. Dwell: press when the move you want is highlighted. Both fire automatically.", vec![
+                ("add", "Change", Box::new(|ctx: &mut Context| ctx.trigger_event(AdjustAssist(AssistChange::ControlMode))) as Box<dyn FnMut(&mut Context)>),
+            ]),
+            ("How long the ship ignores hits after respawning.", vec![
+                ("add", "Decrease", Box::new(|ctx: &mut Context| ctx.trigger_event(AdjustAssist(AssistChange::RespawnInvulnerability(-500)))) as Box<dyn FnMut(&mut Context)>),
+                ("add", "Increase", Box::new(|ctx: &mut Context| ctx.trigger_event(AdjustAssist(AssistChange::RespawnInvulnerability(500)))) as Box<dyn FnMut(&mut Context)>),
+            ]),
+            ("Slows down every ship, bullet and enemy shot.", vec![
+                ("add", "Slower", Box::new(|ctx: &mut Context| ctx.trigger_event(AdjustAssist(AssistChange::GameSpeed(-0.25)))) as Box<dyn FnMut(&mut Context)>),
+                ("add", "Faster", Box::new(|ctx: &mut Context| ctx.trigger_event(AdjustAssist(AssistChange::GameSpeed(0.25)))) as Box<dyn FnMut(&mut Context)>),
+            ]),
         ];
+        buttons.extend(assist_items.into_iter().zip(assists.iter()).map(|((sub, actions), (title, _))| {
+            DataItemSettings::new(ctx, title, sub, actions)
+        }));
+
+        buttons.extend(keys.into_iter().map(|(control, title)| {
+            DataItemSettings::new(ctx, &title, "Press Rebind, then the key you want to use.", vec![
+                ("add", "Rebind", Box::new(move |ctx: &mut Context| ctx.trigger_event(RebindKey(control))) as Box<dyn FnMut(&mut Context)>)
+            ])
+        }));
 
-        let back = IconButton::navigation(ctx, "left", |ctx: &mut Context| ctx.trigger_event(NavigateEvent(0)));
+        buttons.extend(inputs.into_iter().map(|(kind, active)| {
+            let title = format!("{} Input: {}", kind.label(), if active {"On"} else {"Off"});
+            let toggle = if active { "Turn Off" } else { "Turn On" };
+            DataItemSettings::new(ctx, &title, "Allows this input to control the player.", vec![
+                ("add", toggle, Box::new(move |ctx: &mut Context| ctx.trigger_event(ToggleInputSource(kind))) as Box<dyn FnMut(&mut Context)>)
+            ])
+        }));
+
+        #[cfg(feature = "serial")]
+        if let Some(config) = ctx.state().get_mut_or_default::<SharedState>().serial_config.as_ref().and_then(|c| c.lock().ok()).map(|c| c.clone()) {
+            buttons.push(DataItemSettings::new(ctx, &format!("Serial Port: {}", config.path), "Device the wired controller is plugged into.", vec![
+                ("add", "Next Port", Box::new(|ctx: &mut Context| ctx.trigger_event(CycleSerialPort)) as Box<dyn FnMut(&mut Context)>)
+            ]));
+            buttons.push(DataItemSettings::new(ctx, &format!("Serial Baud Rate: {}", config.baud), "Must match Serial.begin() in the controller sketch.", vec![
+                ("add", "Decrease", Box::new(|ctx: &mut Context| ctx.trigger_event(AdjustSerialBaud(-1))) as Box<dyn FnMut(&mut Context)>),
+                ("add", "Increase", Box::new(|ctx: &mut Context| ctx.trigger_event(AdjustSerialBaud(1))) as Box<dyn FnMut(&mut Context)>),
+            ]));
+        }
 
-        let header = Header::stack(ctx, Some(back), "Settings", None);
+        let (code, generation, devices) = ctx.state().get_mut_or_default::<SharedState>().pairing.as_ref().and_then(|p| p.lock().ok())
+            .map(|p| (p.code().to_string(), p.generation(), p.devices())).unwrap_or_default();
+        buttons.push(DataItemSettings::new(ctx, &format!("Pairing Code: {}", code), "New WebSocket controllers send this code when they first connect.", vec![
+            ("add", "New Code", Box::new(|ctx: &mut Context| ctx.trigger_event(NewPairingCode)) as Box<dyn FnMut(&mut Context)>)
+        ]));
+        buttons.extend(devices.iter().map(|(device, connected)| {
+            let title = format!("Controller {}: {}", device, if *connected {"Connected"} else {"Paired"});
+            let device = device.clone();
+            DataItemSettings::new(ctx, &title, "Kicking disconnects it; it needs a new pairing code to come back.", vec![
+                ("add", "Kick", Box::new(move |ctx: &mut Context| ctx.trigger_event(KickController(device.clone()))) as Box<dyn FnMut(&mut Context)>)
+            ])
+        }));
+        let devices = devices.into_iter().map(|(device, _)| device).collect();
+
+        let back_button = IconButton::navigation(ctx, "left", |ctx: &mut Context| ctx.trigger_event(NavigateEvent(0)));
+
+        let header = Header::stack(ctx, Some(back_button), "Settings", None);
         // Convert Vec<DataItem> to Vec<Box<dyn Drawable>>
         let drawable_buttons: Vec<Box<dyn Drawable>> = buttons.into_iter().map(|item| Box::new(item) as Box<dyn Drawable>).collect();
         let content = Content::new(Offset::Start, drawable_buttons);
 
-        Settings(Stack::default(), Page::new(Some(header), content, None), Some(gameboard))
+        Settings(Stack::default(), Page::new(Some(header), content, None), Some(gameboard), None, devices, back, generation)
     }
 }
 
-pub struct DataItemSettings;
+// Position of the pairing code item, after the input sources and serial items; one DataItem per allowed
+// controller follows it.
+fn pairing_index(shared: &SharedState) -> usize {
+    let inputs = shared.inputs.as_ref().and_then(|i| i.lock().ok()).map(|i| i.kinds().len()).unwrap_or(0);
+    #[cfg(feature = "serial")]
+    let inputs = inputs + if shared.serial_config.is_some() { 2 } else { 0 };
+    INPUTS_INDEX + inputs
+}
+
+// Labels for the assist items in page order, with the toggle button text for the on/off ones.
+fn assist_labels(assists: &Assists) -> [(String, Option<&'static str>); 6] {
+    let on_off = |on: bool| (if on {"On"} else {"Off"}, Some(if on {"Turn Off"} else {"Turn On"}));
+    let (aim, aim_toggle) = on_off(assists.aim_assist);
+    [
+        (format!("Assist Preset: {}", assists.current_preset().label()), None),
+        (format!("Enemy Bullet Speed: {:.0}%", assists.enemy_bullet_speed * 100.0), None),
+        (format!("Aim Assist: {}", aim), aim_toggle),
+        (format!("Control Mode: {}", assists.control_mode.label()), None),
+        (format!("Respawn Invulnerability: {}ms", assists.respawn_invulnerability), None),
+        (format!("Game Speed: {:.0}%", assists.game_speed * 100.0), None),
+    ]
+}
 
-impl DataItemSettings {
-    pub fn new(ctx: &mut Context, title: &str, sub: &str, buttons: Vec<(&'static str, &str, Box<dyn FnMut(&mut Context)>)>) -> DataItem {
-        let buttons = buttons.into_iter().map(|(i, n, c)| Button::secondary(ctx, Some(i), n, None, c, None)).collect::<Vec<_>>();
-        DataItem::new(ctx, None, title, Some(sub), None, None, Some(buttons))
+// Auto-shoot fires on the fire cooldown, so its label carries the live interval.
+fn auto_shoot_label(gamestate: &GameState) -> String {
+    match gamestate.player_auto_shoot {
+        true => format!("Player Auto Shoots: Yes (every {}ms)", gamestate.player_fire_cooldown),
+        false => "Player Auto Shoots: No".to_string(),
     }
-}
\ No newline at end of file
+}
+
+fn session_log_sub() -> String {
+    format!("Saves this session's log to {} for export.", logger::log_dir().display())
+}
@@ -0,0 +1,88 @@
+use pelican_ui::Context;
+use pelican_ui_std::Offset;
+use pelican_game_engine::{Sprite, Gameboard};
+use rand::Rng;
+use log::debug;
+
+use crate::game_collection::galaga_game::Art;
+
+// Scrolling background stars. Each star is a plain sprite moved by its adjustments and wrapped back to the top,
+// so a frame costs one position update per star. Every star is inserted once, before anything else on the board
+// so it's drawn behind the ships, and the density is changed by parking stars below the board rather than adding
+// or removing sprites. Parked stars overlap nothing, so they don't add collision events.
+#[derive(Default, Debug, Clone)]
+pub struct Starfield {
+    // (sprite id, layer, parked)
+    stars: Vec<(String, usize, bool)>,
+}
+
+impl Starfield {
+    // (speed, size, art, share of the stars) from the far layer to the near one.
+    const LAYERS: [(f32, f32, Art, f32); 3] = [
+        (0.4, 2.0, Art::StarDim, 0.5),
+        (0.9, 3.0, Art::StarDim, 0.3),
+        (1.6, 4.0, Art::Star, 0.2),
+    ];
+    const MAX_STARS: usize = 48;
+
+    // Later waves get a denser, busier sky, capped so the per-frame cost stays low on phones.
+    pub fn density(wave: u32) -> usize {
+        (16 + wave as usize * 4).min(Self::MAX_STARS)
+    }
+
+    // Inserts every star the field can show, all parked. Called on a fresh board before any other sprite goes in.
+    pub fn build(&mut self, ctx: &mut Context, gameboard: &mut Gameboard) {
+        self.stars.clear();
+        let (width, height) = gameboard.0.size(ctx);
+        let mut rng = rand::thread_rng();
+        for (layer, (_, size, art, share)) in Self::LAYERS.iter().enumerate() {
+            for n in 0..(Self::MAX_STARS as f32 * share).round() as usize {
+                let id = format!("star_{}_{}", layer, n);
+                let x = rng.gen_range(0.0..width.max(1.0));
+                let star = Sprite::new(ctx, &id, art.name(), (*size, *size), (Offset::Static(x), Offset::Static(height + size)));
+                gameboard.insert_sprite(ctx, star);
+                self.stars.push((id, layer, true));
+            }
+        }
+    }
+
+    // Unparks or parks stars until each layer shows its share of `count`. Unparked stars appear anywhere on the
+    // board; parked ones wait just below it.
+    pub fn set_density(&mut self, ctx: &mut Context, gameboard: &mut Gameboard, count: usize) {
+        let (width, height) = gameboard.0.size(ctx);
+        let mut rng = rand::thread_rng();
+        for (layer, (_, size, _, share)) in Self::LAYERS.iter().enumerate() {
+            let want = (count as f32 * share).round() as usize;
+            for (i, (id, _, parked)) in self.stars.iter_mut().filter(|(_, l, _)| *l == layer).enumerate() {
+                let shown = i < want;
+                if shown != *parked { continue; }
+                let Some(star) = gameboard.get_sprite_by_id(id) else { continue };
+                let (x, y) = star.position(ctx);
+                let (to_x, to_y) = match shown {
+                    true => (rng.gen_range(0.0..width.max(1.0)), rng.gen_range(0.0..height.max(1.0))),
+                    false => (x, height + size),
+                };
+                star.adjustments().0 += to_x - x;
+                star.adjustments().1 += to_y - y;
+                *parked = !shown;
+            }
+        }
+        debug!(target: "galaga", "starfield set to {} stars", self.stars.iter().filter(|(_, _, parked)| !parked).count());
+    }
+
+    // Moves every shown star down by its layer's speed, wrapping the ones that leave the bottom to a new column at the top.
+    pub fn scroll(&mut self, ctx: &mut Context, gameboard: &mut Gameboard, speed: f32) {
+        let (width, height) = gameboard.0.size(ctx);
+        let mut rng = rand::thread_rng();
+        for (id, layer, _) in self.stars.iter().filter(|(_, _, parked)| !parked) {
+            let Some(star) = gameboard.get_sprite_by_id(id) else { continue };
+            let (layer_speed, size, _, _) = Self::LAYERS[*layer];
+            star.adjustments().1 += layer_speed * speed;
+            let (x, y) = star.position(ctx);
+            if y > height {
+                star.adjustments().1 -= y + size;
+                star.adjustments().0 += rng.gen_range(0.0..width.max(1.0)) - x;
+            }
+        }
+    }
+}
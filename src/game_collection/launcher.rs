@@ -0,0 +1,42 @@
+use pelican_ui::events::{Event, OnEvent};
+use pelican_ui::drawable::{Drawable, Component};
+use pelican_ui::layout::{Area, SizeRequest, Layout};
+use pelican_ui::{Context, Component};
+use pelican_ui_std::{Stack, Content, Header, Page, Offset, AppPage, Button, DataItem, NavigateEvent};
+
+use crate::game_collection::{registry, Game};
+
+// Lists every registered game. Navigating to 2 * i plays game i and 2 * i + 1 opens its settings.
+#[derive(Debug, Component)]
+pub struct Launcher(Stack, Page, #[skip] Vec<Box<dyn Game>>);
+
+impl OnEvent for Launcher {}
+
+impl AppPage for Launcher {
+    fn has_nav(&self) -> bool {false}
+    fn navigate(self: Box<Self>, ctx: &mut Context, index: usize) -> Result<Box<dyn AppPage>, Box<dyn AppPage>> {
+        let Some(game) = self.2.get(index / 2) else { return Err(self) };
+        match index % 2 {
+            0 => Ok(game.page(ctx)),
+            _ => game.settings(ctx).ok_or(self),
+        }
+    }
+}
+
+impl Launcher {
+    pub fn new(ctx: &mut Context) -> Self {
+        let games = registry();
+
+        let items = games.iter().enumerate().map(|(i, game)| {
+            let play = Button::secondary(ctx, Some(game.icon()), "Play", None, move |ctx: &mut Context| ctx.trigger_event(NavigateEvent(2 * i)), None);
+            let settings = Button::secondary(ctx, Some("settings"), "Settings", None, move |ctx: &mut Context| ctx.trigger_event(NavigateEvent(2 * i + 1)), None);
+            DataItem::new(ctx, None, game.name(), Some(game.description()), None, None, Some(vec![play, settings]))
+        }).collect::<Vec<_>>();
+
+        let header = Header::stack(ctx, None, "Ramp Games", None);
+        let drawable_items: Vec<Box<dyn Drawable>> = items.into_iter().map(|item| Box::new(item) as Box<dyn Drawable>).collect();
+        let content = Content::new(Offset::Start, drawable_items);
+
+        Launcher(Stack::default(), Page::new(Some(header), content, None), games)
+    }
+}
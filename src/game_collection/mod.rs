@@ -1 +1,48 @@
-pub mod galaga_game;
\ No newline at end of file
+use pelican_ui::Context;
+use pelican_ui_std::AppPage;
+use pelican_game_engine::Gameboard;
+
+#[cfg(feature = "galaga")]
+pub mod galaga_game;
+#[cfg(feature = "breakout")]
+pub mod breakout_game;
+#[cfg(feature = "flap")]
+pub mod flap_game;
+pub mod launcher;
+pub mod assets;
+// Controllers, profiles and session metrics, used by every game.
+pub mod shared;
+
+// What the launcher needs from a game. Each game lives in its own module behind its own cargo feature.
+pub trait Game: std::fmt::Debug {
+    fn name(&self) -> &'static str;
+    fn description(&self) -> &'static str;
+    // Icon on the game's play button in the launcher.
+    fn icon(&self) -> &'static str;
+    // File under ./manifests listing the game's illustrations.
+    fn manifest(&self) -> &'static str;
+    // Every illustration name the game's sprites use, checked against the manifest at startup.
+    fn illustrations(&self) -> Vec<&'static str>;
+    fn page(&self, ctx: &mut Context) -> Box<dyn AppPage>;
+    fn settings(&self, ctx: &mut Context) -> Option<Box<dyn AppPage>>;
+}
+
+// Page to go to when a game's settings are closed, built from the board they were holding.
+pub type ReturnTo = fn(&mut Context, Gameboard) -> Box<dyn AppPage>;
+
+// For settings opened from the launcher, which have no game to go back to.
+pub fn to_launcher(ctx: &mut Context, _gameboard: Gameboard) -> Box<dyn AppPage> {
+    Box::new(launcher::Launcher::new(ctx))
+}
+
+// Every game compiled into this build, in launcher order.
+pub fn registry() -> Vec<Box<dyn Game>> {
+    vec![
+        #[cfg(feature = "galaga")]
+        Box::new(galaga_game::GalagaGame),
+        #[cfg(feature = "breakout")]
+        Box::new(breakout_game::BreakoutGame),
+        #[cfg(feature = "flap")]
+        Box::new(flap_game::FlapGame),
+    ]
+}
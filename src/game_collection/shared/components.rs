@@ -0,0 +1,12 @@
+use pelican_ui::Context;
+use pelican_ui_std::{Button, DataItem};
+
+// A settings row: a data item with a secondary button per (icon, label, action).
+pub struct DataItemSettings;
+
+impl DataItemSettings {
+    pub fn new(ctx: &mut Context, title: &str, sub: &str, buttons: Vec<(&'static str, &str, Box<dyn FnMut(&mut Context)>)>) -> DataItem {
+        let buttons = buttons.into_iter().map(|(i, n, c)| Button::secondary(ctx, Some(i), n, None, c, None)).collect::<Vec<_>>();
+        DataItem::new(ctx, None, title, Some(sub), None, None, Some(buttons))
+    }
+}
\ No newline at end of file
@@ -0,0 +1,29 @@
+use std::sync::mpsc::Sender;
+use std::sync::{Arc, Mutex};
+use std::collections::VecDeque;
+use serde_json::{json, Value};
+
+// Shared secret QA clients must send before any console command is accepted. The console is off when it isn't set.
+const TOKEN_VAR: &str = "GALAGA_CONSOLE_TOKEN";
+
+// Console messages from authenticated clients, queued by the server thread with the sender their acknowledgement
+// goes back through. The game that's running reads and applies them.
+pub type ConsoleQueue = Arc<Mutex<VecDeque<(Value, Sender<String>)>>>;
+
+// The token a client has to authenticate with, if the console is turned on.
+pub fn token() -> Option<String> {
+    std::env::var(TOKEN_VAR).ok().filter(|t| !t.is_empty())
+}
+
+// Compares without stopping at the first wrong byte, so the token can't be guessed one character at a time.
+pub fn token_matches(given: &str, expected: &str) -> bool {
+    given.len() == expected.len() && given.bytes().zip(expected.bytes()).fold(0, |diff, (a, b)| diff | (a ^ b)) == 0
+}
+
+// Reply sent back to the console client for each command.
+pub fn ack(command: &str, result: &Result<String, String>) -> String {
+    match result {
+        Ok(message) => json!({"ack": command, "ok": true, "message": message}).to_string(),
+        Err(error) => json!({"ack": command, "ok": false, "error": error}).to_string(),
+    }
+}
@@ -0,0 +1,21 @@
+use pelican_ui::events::Event;
+use pelican_ui::Context;
+
+use crate::game_collection::shared::server::GameAction;
+use crate::game_collection::shared::metrics::ExportFormat;
+
+#[derive(Clone, Debug)]
+pub struct TouchControlEvent(pub GameAction);
+impl Event for TouchControlEvent {
+    fn pass(self: Box<Self>, _ctx: &mut Context, children: Vec<((f32, f32), (f32, f32))>) -> Vec<Option<Box<dyn Event>>> {
+        children.into_iter().map(|_| Some(self.clone() as Box<dyn Event>)).collect()
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct ExportSession(pub ExportFormat);
+impl Event for ExportSession {
+    fn pass(self: Box<Self>, _ctx: &mut Context, children: Vec<((f32, f32), (f32, f32))>) -> Vec<Option<Box<dyn Event>>> {
+        children.into_iter().map(|_| Some(self.clone() as Box<dyn Event>)).collect()
+    }
+}
\ No newline at end of file
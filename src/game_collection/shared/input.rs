@@ -0,0 +1,387 @@
+use std::fmt::Debug;
+use std::sync::{Arc, Mutex};
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+use pelican_ui::events::{Event, KeyboardEvent, KeyboardState, MouseEvent, MouseState};
+use log::info;
+
+use crate::game_collection::shared::server::{ArduinoServer, GameAction};
+use crate::game_collection::shared::events::TouchControlEvent;
+use crate::game_collection::shared::keybindings::{Control, KeyBindings};
+use crate::game_collection::shared::metrics::SharedMetrics;
+use crate::game_collection::shared::console::ConsoleQueue;
+use crate::game_collection::shared::pairing::SharedPairing;
+#[cfg(feature = "serial")]
+use crate::game_collection::shared::serial::{SerialConfig, SerialReader};
+
+pub type ActionQueue = Arc<Mutex<VecDeque<GameAction>>>;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InputKind {
+    Keyboard,
+    Touch,
+    WebSocket,
+    Gamepad,
+    Serial,
+    Bot,
+}
+
+impl InputKind {
+    pub fn label(&self) -> &'static str {
+        match self {
+            InputKind::Keyboard => "Keyboard",
+            InputKind::Touch => "Touch",
+            InputKind::WebSocket => "WebSocket Controller",
+            InputKind::Gamepad => "Gamepad",
+            InputKind::Serial => "USB Serial",
+            InputKind::Bot => "Demo Bot",
+        }
+    }
+}
+
+// Anything that can drive the player. Sources that react to ui events (keyboard, touch) translate them in `event`,
+// sources fed from another thread or a timer (websocket, gamepad, bot) hand over what they have in `poll`.
+pub trait InputSource: Debug + Send {
+    fn kind(&self) -> InputKind;
+
+    fn event(&mut self, _event: &mut dyn Event) -> Vec<GameAction> {
+        Vec::new()
+    }
+
+    fn poll(&mut self) -> Vec<GameAction> {
+        Vec::new()
+    }
+}
+
+// Fields: sources paired with whether they are currently active
+#[derive(Debug, Default)]
+pub struct InputSources(Vec<(Box<dyn InputSource>, bool)>);
+
+impl InputSources {
+    pub fn new() -> Self {
+        InputSources(Vec::new())
+    }
+
+    pub fn add(&mut self, source: Box<dyn InputSource>, active: bool) {
+        info!(target: "galaga", "registered {} input (active: {})", source.kind().label(), active);
+        self.0.push((source, active));
+    }
+
+    pub fn kinds(&self) -> Vec<InputKind> {
+        self.0.iter().map(|(s, _)| s.kind()).collect()
+    }
+
+    pub fn is_active(&self, kind: InputKind) -> bool {
+        self.0.iter().any(|(s, a)| s.kind() == kind && *a)
+    }
+
+    pub fn set_active(&mut self, kind: InputKind, active: bool) {
+        self.0.iter_mut().filter(|(s, _)| s.kind() == kind).for_each(|(_, a)| *a = active);
+    }
+
+    pub fn toggle(&mut self, kind: InputKind) -> bool {
+        let active = !self.is_active(kind);
+        self.set_active(kind, active);
+        active
+    }
+
+    pub fn event(&mut self, event: &mut dyn Event, queue: &ActionQueue) {
+        let actions = self.0.iter_mut().filter(|(_, a)| *a).flat_map(|(s, _)| s.event(event)).collect::<Vec<_>>();
+        Self::push(queue, actions);
+    }
+
+    // Inactive sources are still drained so their backlog doesn't replay when they are switched back on.
+    pub fn poll(&mut self, queue: &ActionQueue) {
+        let actions = self.0.iter_mut().flat_map(|(s, a)| {
+            let actions = s.poll();
+            if *a { actions } else { Vec::new() }
+        }).collect::<Vec<_>>();
+        Self::push(queue, actions);
+    }
+
+    fn push(queue: &ActionQueue, actions: Vec<GameAction>) {
+        if actions.is_empty() { return; }
+        if let Ok(mut queue) = queue.lock() {
+            queue.extend(actions);
+        }
+    }
+}
+
+// Fields: shared key bindings, bound controls currently held (oldest first), time hold-to-fire last shot
+#[derive(Debug)]
+pub struct KeyboardInput(Arc<Mutex<KeyBindings>>, Vec<Control>, Option<Instant>);
+
+impl KeyboardInput {
+    // Holding the fire key shoots at most this often.
+    const HOLD_FIRE_INTERVAL: Duration = Duration::from_millis(250);
+
+    pub fn new(bindings: Arc<Mutex<KeyBindings>>) -> Self {
+        KeyboardInput(bindings, Vec::new(), None)
+    }
+
+    // The most recently pressed direction that is still held wins, so releasing one of two held arrows keeps moving.
+    fn movement(&self) -> GameAction {
+        match self.1.iter().rev().find(|c| matches!(c, Control::Left | Control::Right)) {
+            Some(Control::Left) => GameAction::MoveLeft,
+            Some(Control::Right) => GameAction::MoveRight,
+            _ => GameAction::StopMoving,
+        }
+    }
+
+    fn fire(&mut self) -> Option<GameAction> {
+        if self.2.is_some_and(|t| t.elapsed() < Self::HOLD_FIRE_INTERVAL) { return None; }
+        self.2 = Some(Instant::now());
+        Some(GameAction::Shoot)
+    }
+}
+
+impl InputSource for KeyboardInput {
+    fn kind(&self) -> InputKind { InputKind::Keyboard }
+
+    fn event(&mut self, event: &mut dyn Event) -> Vec<GameAction> {
+        let Some(KeyboardEvent { state, key }) = event.downcast_ref::<KeyboardEvent>() else { return Vec::new() };
+        let Some(control) = self.0.lock().ok().and_then(|b| b.control(key)) else { return Vec::new() };
+
+        match state {
+            KeyboardState::Pressed => {
+                // Ignore auto-repeat for keys we already know are down.
+                if self.1.contains(&control) { return Vec::new(); }
+                self.1.push(control);
+                match control {
+                    Control::Left | Control::Right => vec![self.movement()],
+                    Control::Fire => self.fire().into_iter().collect(),
+                    Control::Pause => vec![GameAction::Pause],
+                    Control::Settings => vec![GameAction::OpenSettings],
+                }
+            }
+            KeyboardState::Released => {
+                self.1.retain(|c| *c != control);
+                match control {
+                    Control::Left | Control::Right => vec![self.movement()],
+                    _ => Vec::new(),
+                }
+            }
+        }
+    }
+
+    fn poll(&mut self) -> Vec<GameAction> {
+        if !self.1.contains(&Control::Fire) { return Vec::new(); }
+        self.fire().into_iter().collect()
+    }
+}
+
+// Fields: direction the on-screen buttons or a drag last set, x position the current drag is measured from
+#[derive(Debug, Default)]
+pub struct TouchInput(Option<GameAction>, Option<f32>);
+
+impl TouchInput {
+    // Distance a finger has to travel across the gameboard before a drag changes direction.
+    const DRAG_THRESHOLD: f32 = 4.0;
+
+    // Touch is the only built-in control on phones, so it starts switched on there.
+    pub fn default_active() -> bool {
+        cfg!(any(target_os = "ios", target_os = "android"))
+    }
+
+    fn steer(&mut self, action: GameAction) -> Vec<GameAction> {
+        if self.0.as_ref() == Some(&action) { return Vec::new(); }
+        self.0 = Some(action.clone());
+        vec![action]
+    }
+}
+
+impl InputSource for TouchInput {
+    fn kind(&self) -> InputKind { InputKind::Touch }
+
+    fn event(&mut self, event: &mut dyn Event) -> Vec<GameAction> {
+        if let Some(TouchControlEvent(action)) = event.downcast_ref::<TouchControlEvent>() {
+            return match action {
+                // Tapping the direction the ship is already moving in stops it.
+                GameAction::MoveLeft | GameAction::MoveRight if self.0.as_ref() == Some(action) => {
+                    self.0 = None;
+                    vec![GameAction::StopMoving]
+                }
+                GameAction::MoveLeft | GameAction::MoveRight => self.steer(action.clone()),
+                other => vec![other.clone()],
+            };
+        }
+
+        match event.downcast_ref::<MouseEvent>() {
+            Some(MouseEvent { state: MouseState::Pressed, position: Some((x, _)) }) => {
+                self.1 = Some(*x);
+                Vec::new()
+            }
+            Some(MouseEvent { state: MouseState::Moved, position: Some((x, _)) }) => {
+                let Some(anchor) = self.1 else { return Vec::new() };
+                let dx = *x - anchor;
+                if dx.abs() < Self::DRAG_THRESHOLD { return Vec::new(); }
+                self.1 = Some(*x);
+                self.steer(if dx < 0.0 { GameAction::MoveLeft } else { GameAction::MoveRight })
+            }
+            Some(MouseEvent { state: MouseState::Released, .. }) if self.1.is_some() => {
+                self.1 = None;
+                self.0 = None;
+                vec![GameAction::StopMoving]
+            }
+            _ => Vec::new(),
+        }
+    }
+}
+
+// Fields: queue filled by the ArduinoServer thread
+#[derive(Debug)]
+pub struct WebSocketInput(ActionQueue);
+
+impl WebSocketInput {
+    pub fn start(port: u16, metrics: SharedMetrics, console: ConsoleQueue, pairing: SharedPairing) -> Self {
+        let mut arduino_server = ArduinoServer::new(port);
+        arduino_server.set_metrics(metrics);
+        arduino_server.set_console(console);
+        arduino_server.set_pairing(pairing);
+        let action_queue = arduino_server.get_action_queue();
+        let _server_handle = arduino_server.start();
+        info!(target: "galaga", "Arduino WebSocket server started in background thread");
+        WebSocketInput(action_queue)
+    }
+}
+
+impl InputSource for WebSocketInput {
+    fn kind(&self) -> InputKind { InputKind::WebSocket }
+
+    fn poll(&mut self) -> Vec<GameAction> {
+        self.0.lock().map(|mut q| q.drain(..).collect()).unwrap_or_default()
+    }
+}
+
+// Fields: queue filled by the gamepad thread
+#[cfg(feature = "gamepad")]
+#[derive(Debug)]
+pub struct GamepadInput(ActionQueue);
+
+#[cfg(feature = "gamepad")]
+impl GamepadInput {
+    // Stick positions inside this range count as centred.
+    const DEADZONE: f32 = 0.3;
+
+    pub fn start() -> Self {
+        use gilrs::{Axis, Button, EventType, Gilrs};
+        use log::warn;
+
+        let queue: ActionQueue = Arc::new(Mutex::new(VecDeque::new()));
+        let thread_queue = queue.clone();
+        std::thread::spawn(move || {
+            let mut gilrs = match Gilrs::new() {
+                Ok(gilrs) => gilrs,
+                Err(e) => {
+                    warn!(target: "galaga", "Gamepad support unavailable: {}", e);
+                    return;
+                }
+            };
+            let mut stick = 0;
+            loop {
+                while let Some(gilrs::Event { event, .. }) = gilrs.next_event() {
+                    let action = match event {
+                        EventType::ButtonPressed(Button::DPadLeft, _) => Some(GameAction::MoveLeft),
+                        EventType::ButtonPressed(Button::DPadRight, _) => Some(GameAction::MoveRight),
+                        EventType::ButtonReleased(Button::DPadLeft | Button::DPadRight, _) => Some(GameAction::StopMoving),
+                        EventType::ButtonPressed(Button::South | Button::RightTrigger, _) => Some(GameAction::Shoot),
+                        EventType::ButtonPressed(Button::Start, _) => Some(GameAction::Pause),
+                        EventType::AxisChanged(Axis::LeftStickX, value, _) => {
+                            let direction = if value < -Self::DEADZONE { -1 } else if value > Self::DEADZONE { 1 } else { 0 };
+                            if direction == stick { None } else {
+                                stick = direction;
+                                Some(match direction {
+                                    -1 => GameAction::MoveLeft,
+                                    1 => GameAction::MoveRight,
+                                    _ => GameAction::StopMoving,
+                                })
+                            }
+                        }
+                        _ => None,
+                    };
+                    if let (Some(action), Ok(mut queue)) = (action, thread_queue.lock()) {
+                        queue.push_back(action);
+                    }
+                }
+                std::thread::sleep(Duration::from_millis(8));
+            }
+        });
+        GamepadInput(queue)
+    }
+}
+
+#[cfg(feature = "gamepad")]
+impl InputSource for GamepadInput {
+    fn kind(&self) -> InputKind { InputKind::Gamepad }
+
+    fn poll(&mut self) -> Vec<GameAction> {
+        self.0.lock().map(|mut q| q.drain(..).collect()).unwrap_or_default()
+    }
+}
+
+// Fields: queue filled by the SerialReader thread
+#[cfg(feature = "serial")]
+#[derive(Debug)]
+pub struct SerialInput(ActionQueue);
+
+#[cfg(feature = "serial")]
+impl SerialInput {
+    pub fn start(config: Arc<Mutex<SerialConfig>>, metrics: SharedMetrics) -> Self {
+        let mut reader = SerialReader::new(config);
+        reader.set_metrics(metrics);
+        let action_queue = reader.get_action_queue();
+        let _reader_handle = reader.start();
+        SerialInput(action_queue)
+    }
+}
+
+#[cfg(feature = "serial")]
+impl InputSource for SerialInput {
+    fn kind(&self) -> InputKind { InputKind::Serial }
+
+    fn poll(&mut self) -> Vec<GameAction> {
+        self.0.lock().map(|mut q| q.drain(..).collect()).unwrap_or_default()
+    }
+}
+
+// Fields: looping script of (delay before action, action), index of the next step, time the previous step fired
+#[derive(Debug)]
+pub struct BotInput(Vec<(Duration, GameAction)>, usize, Instant);
+
+impl BotInput {
+    pub fn new(script: Vec<(Duration, GameAction)>) -> Self {
+        BotInput(script, 0, Instant::now())
+    }
+
+    // Sweeps across the board firing as it goes; handy for soak tests and attract mode.
+    pub fn demo() -> Self {
+        let ms = Duration::from_millis;
+        BotInput::new(vec![
+            (ms(0), GameAction::MoveLeft),
+            (ms(300), GameAction::Shoot),
+            (ms(600), GameAction::Shoot),
+            (ms(300), GameAction::StopMoving),
+            (ms(200), GameAction::MoveRight),
+            (ms(300), GameAction::Shoot),
+            (ms(600), GameAction::Shoot),
+            (ms(300), GameAction::StopMoving),
+            (ms(200), GameAction::Shoot),
+        ])
+    }
+}
+
+impl InputSource for BotInput {
+    fn kind(&self) -> InputKind { InputKind::Bot }
+
+    fn poll(&mut self) -> Vec<GameAction> {
+        let mut actions = Vec::new();
+        for _ in 0..self.0.len() {
+            let (delay, action) = self.0[self.1].clone();
+            if self.2.elapsed() < delay { break; }
+            actions.push(action);
+            self.2 = Instant::now();
+            self.1 = (self.1 + 1) % self.0.len();
+        }
+        actions
+    }
+}
@@ -0,0 +1,127 @@
+use pelican_ui::events::{Key, NamedKey};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Control {
+    Left,
+    Right,
+    Fire,
+    Pause,
+    Settings,
+}
+
+impl Control {
+    pub const ALL: [Control; 5] = [Control::Left, Control::Right, Control::Fire, Control::Pause, Control::Settings];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Control::Left => "Move Left",
+            Control::Right => "Move Right",
+            Control::Fire => "Fire",
+            Control::Pause => "Pause",
+            Control::Settings => "Settings",
+        }
+    }
+}
+
+// Fields: every (control, key) pair; a control can have several keys but a key drives only one control
+#[derive(Debug, Clone)]
+pub struct KeyBindings(Vec<(Control, Key)>);
+
+impl Default for KeyBindings {
+    fn default() -> Self {
+        KeyBindings(vec![
+            (Control::Left, Key::Named(NamedKey::ArrowLeft)),
+            (Control::Left, Key::Character("a".into())),
+            (Control::Right, Key::Named(NamedKey::ArrowRight)),
+            (Control::Right, Key::Character("d".into())),
+            (Control::Fire, Key::Named(NamedKey::ArrowUp)),
+            (Control::Fire, Key::Named(NamedKey::Space)),
+            (Control::Fire, Key::Character("w".into())),
+            (Control::Pause, Key::Character("p".into())),
+            (Control::Settings, Key::Named(NamedKey::Escape)),
+        ])
+    }
+}
+
+impl KeyBindings {
+    // Named keys that can be saved in a profile; anything else falls back to the default binding on load.
+    const NAMED: [NamedKey; 12] = [
+        NamedKey::ArrowLeft, NamedKey::ArrowRight, NamedKey::ArrowUp, NamedKey::ArrowDown,
+        NamedKey::Space, NamedKey::Enter, NamedKey::Escape, NamedKey::Tab,
+        NamedKey::Backspace, NamedKey::Shift, NamedKey::Control, NamedKey::Alt,
+    ];
+
+    // Bindings as (control, key label) pairs for saving. Keys whose label wouldn't load back are left out.
+    pub fn to_names(&self) -> Vec<(Control, String)> {
+        self.0.iter().filter(|(_, k)| Self::can_bind(k)).map(|(c, k)| (*c, Self::key_label(k))).collect()
+    }
+
+    pub fn from_names(names: &[(Control, String)]) -> Self {
+        let mut bindings = KeyBindings(Vec::new());
+        for (control, name) in names {
+            if let Some(key) = Self::parse_label(name) {
+                bindings.0.push((*control, key));
+            }
+        }
+        // Controls left without a key get their defaults back so a bad file can't lock the player out.
+        for (control, key) in KeyBindings::default().0 {
+            if bindings.keys(control).is_empty() && bindings.control(&key).is_none() {
+                bindings.0.push((control, key));
+            }
+        }
+        bindings
+    }
+
+    // Inverse of `key_label`.
+    pub fn parse_label(label: &str) -> Option<Key> {
+        match label.chars().count() {
+            1 => Some(Key::Character(label.to_lowercase().into())),
+            _ => Self::NAMED.into_iter().find(|n| Self::key_label(&Key::Named(n.clone())) == label).map(Key::Named),
+        }
+    }
+
+    pub fn control(&self, key: &Key) -> Option<Control> {
+        self.0.iter().find(|(_, k)| Self::same_key(k, key)).map(|(c, _)| *c)
+    }
+
+    pub fn keys(&self, control: Control) -> Vec<&Key> {
+        self.0.iter().filter(|(c, _)| *c == control).map(|(_, k)| k).collect()
+    }
+
+    // Whether the key's label parses back to the same key, so the binding survives a save and load.
+    pub fn can_bind(key: &Key) -> bool {
+        Self::parse_label(&Self::key_label(key)).is_some_and(|parsed| Self::same_key(&parsed, key))
+    }
+
+    // Makes `key` the only key for `control`, taking it away from whatever control had it before.
+    // Returns false, changing nothing, for keys that can't be saved.
+    pub fn rebind(&mut self, control: Control, key: Key) -> bool {
+        if !Self::can_bind(&key) { return false; }
+        self.0.retain(|(c, k)| *c != control && !Self::same_key(k, &key));
+        self.0.push((control, key));
+        true
+    }
+
+    pub fn describe(&self, control: Control) -> String {
+        let keys = self.keys(control).into_iter().map(Self::key_label).collect::<Vec<_>>();
+        if keys.is_empty() { "Unbound".to_string() } else { keys.join(" / ") }
+    }
+
+    pub fn key_label(key: &Key) -> String {
+        match key {
+            Key::Named(NamedKey::Space) => "Space".to_string(),
+            Key::Named(named) => format!("{:?}", named),
+            Key::Character(c) => c.to_uppercase(),
+            _ => "?".to_string(),
+        }
+    }
+
+    // Letters match regardless of shift so holding shift doesn't disable WASD.
+    fn same_key(a: &Key, b: &Key) -> bool {
+        match (a, b) {
+            (Key::Character(a), Key::Character(b)) => a.to_lowercase() == b.to_lowercase(),
+            (a, b) => a == b,
+        }
+    }
+}
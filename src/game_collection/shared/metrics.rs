@@ -0,0 +1,179 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+use serde::{Deserialize, Serialize};
+
+use crate::game_collection::shared::server::GameAction;
+
+pub type SharedMetrics = Arc<Mutex<SessionMetrics>>;
+
+#[derive(Debug, Clone, Copy)]
+pub enum ExportFormat {
+    Csv,
+    Json,
+}
+
+// Everything collected between the start of a game and its game over.
+#[derive(Debug, Clone)]
+pub struct SessionMetrics {
+    game: &'static str,
+    started_at: u64,
+    started: Instant,
+    ended: Option<Instant>,
+    // Time of the first enemy shot aimed at the player that hasn't been dodged yet.
+    threat: Option<Instant>,
+    actions: BTreeMap<&'static str, u32>,
+    peaks: BTreeMap<&'static str, f64>,
+    reaction_times: Vec<Duration>,
+    shots: u32,
+    hits: u32,
+    lives_lost: u32,
+    score: u32,
+}
+
+// Flattened view of a session, which is what gets exported.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SessionReport {
+    #[serde(default)]
+    pub game: String,
+    pub started_at: u64,
+    pub session_secs: f32,
+    pub score: u32,
+    pub lives_lost: u32,
+    pub shots: u32,
+    pub hits: u32,
+    pub accuracy: f32,
+    pub dodges: usize,
+    pub mean_reaction_ms: Option<u64>,
+    pub reaction_times_ms: Vec<u64>,
+    pub actions: BTreeMap<String, u32>,
+    pub peaks: BTreeMap<String, f64>,
+}
+
+impl SessionMetrics {
+    // A threat nobody reacted to within this long doesn't count as a dodge.
+    const DODGE_WINDOW: Duration = Duration::from_secs(3);
+
+    pub fn new(game: &'static str) -> Self {
+        SessionMetrics {
+            game,
+            started_at: SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_secs()).unwrap_or(0),
+            started: Instant::now(),
+            ended: None,
+            threat: None,
+            actions: BTreeMap::new(),
+            peaks: BTreeMap::new(),
+            reaction_times: Vec::new(),
+            shots: 0,
+            hits: 0,
+            lives_lost: 0,
+            score: 0,
+        }
+    }
+
+    pub fn game(&self) -> &'static str {
+        self.game
+    }
+
+    // Counts every action that reaches the game; a move while a shot is incoming counts as a dodge.
+    pub fn action(&mut self, action: &GameAction) {
+        *self.actions.entry(action.channel()).or_default() += 1;
+        if matches!(action, GameAction::MoveLeft | GameAction::MoveRight) {
+            if let Some(threat) = self.threat.take() {
+                if threat.elapsed() <= Self::DODGE_WINDOW {
+                    self.reaction_times.push(threat.elapsed());
+                }
+            }
+        }
+    }
+
+    // Sensor reading that came with an action.
+    pub fn peak(&mut self, action: &GameAction, value: f64) {
+        let peak = self.peaks.entry(action.channel()).or_insert(value);
+        *peak = peak.max(value);
+    }
+
+    // An enemy fired into the player's column.
+    pub fn threat(&mut self) {
+        if self.threat.is_none_or(|t| t.elapsed() > Self::DODGE_WINDOW) {
+            self.threat = Some(Instant::now());
+        }
+    }
+
+    pub fn shot(&mut self) {
+        self.shots += 1;
+    }
+
+    pub fn hit(&mut self) {
+        self.hits += 1;
+    }
+
+    pub fn life_lost(&mut self) {
+        self.lives_lost += 1;
+    }
+
+    pub fn end(&mut self, score: u32) {
+        self.score = score;
+        self.ended = Some(Instant::now());
+    }
+
+    pub fn report(&self) -> SessionReport {
+        let length = self.ended.unwrap_or_else(Instant::now) - self.started;
+        let reaction_times_ms = self.reaction_times.iter().map(|d| d.as_millis() as u64).collect::<Vec<_>>();
+        SessionReport {
+            game: self.game.to_string(),
+            started_at: self.started_at,
+            session_secs: length.as_secs_f32(),
+            score: self.score,
+            lives_lost: self.lives_lost,
+            shots: self.shots,
+            hits: self.hits,
+            accuracy: if self.shots == 0 { 0.0 } else { self.hits as f32 / self.shots as f32 },
+            dodges: reaction_times_ms.len(),
+            mean_reaction_ms: (!reaction_times_ms.is_empty()).then(|| reaction_times_ms.iter().sum::<u64>() / reaction_times_ms.len() as u64),
+            reaction_times_ms,
+            actions: self.actions.iter().map(|(c, n)| (c.to_string(), *n)).collect(),
+            peaks: self.peaks.iter().map(|(c, p)| (c.to_string(), *p)).collect(),
+        }
+    }
+}
+
+impl SessionReport {
+    // One metric per row so the file opens cleanly in a spreadsheet.
+    pub fn to_csv(&self) -> String {
+        let mut rows = vec![
+            ("game".to_string(), self.game.clone()),
+            ("started_at".to_string(), self.started_at.to_string()),
+            ("session_secs".to_string(), format!("{:.1}", self.session_secs)),
+            ("score".to_string(), self.score.to_string()),
+            ("lives_lost".to_string(), self.lives_lost.to_string()),
+            ("shots".to_string(), self.shots.to_string()),
+            ("hits".to_string(), self.hits.to_string()),
+            ("accuracy".to_string(), format!("{:.3}", self.accuracy)),
+            ("dodges".to_string(), self.dodges.to_string()),
+            ("mean_reaction_ms".to_string(), self.mean_reaction_ms.map(|m| m.to_string()).unwrap_or_default()),
+        ];
+        rows.extend(self.actions.iter().map(|(channel, count)| (format!("actions_{}", channel), count.to_string())));
+        rows.extend(self.peaks.iter().map(|(channel, peak)| (format!("peak_{}", channel), peak.to_string())));
+        rows.extend(self.reaction_times_ms.iter().enumerate().map(|(i, ms)| (format!("reaction_{}_ms", i + 1), ms.to_string())));
+
+        let mut csv = "metric,value\n".to_string();
+        rows.into_iter().for_each(|(metric, value)| csv.push_str(&format!("{},{}\n", metric, value)));
+        csv
+    }
+
+    // Reports sit next to the session logs so both can be pulled off the device together.
+    pub fn export(&self, format: ExportFormat) -> std::io::Result<PathBuf> {
+        let dir = crate::logger::log_dir().with_file_name("sessions");
+        fs::create_dir_all(&dir)?;
+        let (extension, contents) = match format {
+            ExportFormat::Csv => ("csv", self.to_csv()),
+            ExportFormat::Json => ("json", serde_json::to_string_pretty(self).map_err(std::io::Error::other)?),
+        };
+        let path = dir.join(format!("session-{}-{}.{}", self.game.to_lowercase(), self.started_at, extension));
+        fs::write(&path, contents)?;
+        Ok(path)
+    }
+}
@@ -0,0 +1,136 @@
+pub mod input;
+pub mod server;
+pub mod protocol;
+pub mod pairing;
+pub mod console;
+pub mod keybindings;
+#[cfg(feature = "serial")]
+pub mod serial;
+pub mod metrics;
+pub mod profiles;
+pub mod report;
+pub mod components;
+pub mod events;
+
+use std::sync::{Arc, Mutex};
+use std::collections::VecDeque;
+use pelican_ui::Context;
+use pelican_ui::events::{Event, TickEvent};
+
+use crate::game_collection::shared::input::{ActionQueue, InputSources, KeyboardInput, TouchInput, WebSocketInput, BotInput};
+use crate::game_collection::shared::server::GameAction;
+use crate::game_collection::shared::console::ConsoleQueue;
+use crate::game_collection::shared::pairing::{Pairing, SharedPairing};
+use crate::game_collection::shared::keybindings::KeyBindings;
+#[cfg(feature = "serial")]
+use crate::game_collection::shared::serial::SerialConfig;
+use crate::game_collection::shared::metrics::{SessionMetrics, SharedMetrics};
+use crate::game_collection::shared::profiles::Profile;
+
+// The controllers, active profile and session metrics every game plays with. Started by whichever game is
+// opened first and kept for the rest of the run, so switching games doesn't restart the server.
+#[derive(Debug, Default, Clone)]
+pub struct SharedState {
+    pub action_queue: Option<ActionQueue>,
+    pub console: Option<ConsoleQueue>,
+    pub pairing: Option<SharedPairing>,
+    pub inputs: Option<Arc<Mutex<InputSources>>>,
+    pub key_bindings: Option<Arc<Mutex<KeyBindings>>>,
+    #[cfg(feature = "serial")]
+    pub serial_config: Option<Arc<Mutex<SerialConfig>>>,
+    pub metrics: Option<SharedMetrics>,
+    pub profile: Option<String>,
+}
+
+impl SharedState {
+    fn new() -> Self {
+        let key_bindings = Arc::new(Mutex::new(KeyBindings::default()));
+        let metrics = Arc::new(Mutex::new(SessionMetrics::new("Galaga")));
+        let console: ConsoleQueue = Arc::new(Mutex::new(VecDeque::new()));
+        let pairing = Arc::new(Mutex::new(Pairing::load()));
+        let mut inputs = InputSources::new();
+        inputs.add(Box::new(KeyboardInput::new(key_bindings.clone())), true);
+        inputs.add(Box::new(TouchInput::default()), TouchInput::default_active());
+        inputs.add(Box::new(WebSocketInput::start(3030, metrics.clone(), console.clone(), pairing.clone())), true);
+        #[cfg(feature = "gamepad")]
+        inputs.add(Box::new(crate::game_collection::shared::input::GamepadInput::start()), true);
+        #[cfg(feature = "serial")]
+        let serial_config = Arc::new(Mutex::new(SerialConfig::default()));
+        #[cfg(feature = "serial")]
+        inputs.add(Box::new(crate::game_collection::shared::input::SerialInput::start(serial_config.clone(), metrics.clone())), true);
+        inputs.add(Box::new(BotInput::demo()), false);
+
+        SharedState {
+            action_queue: Some(Arc::new(Mutex::new(VecDeque::new()))),
+            console: Some(console),
+            pairing: Some(pairing),
+            inputs: Some(Arc::new(Mutex::new(inputs))),
+            key_bindings: Some(key_bindings),
+            #[cfg(feature = "serial")]
+            serial_config: Some(serial_config),
+            metrics: Some(metrics),
+            profile: None,
+        }
+    }
+
+    // Starts the inputs the first time any game asks, picking up the last active profile.
+    pub fn ensure(ctx: &mut Context) {
+        if ctx.state().get::<SharedState>().is_some() { return; }
+        let mut state = SharedState::new();
+        if let Some(profile) = Profile::active().and_then(|name| Profile::load(&name)) {
+            state.load_profile(&profile);
+        }
+        ctx.state().set(state);
+    }
+
+    // Switches to a profile; key bindings are replaced in place so the keyboard input picks them up.
+    pub fn load_profile(&mut self, profile: &Profile) {
+        if let Some(mut bindings) = self.key_bindings.as_ref().and_then(|b| b.lock().ok()) {
+            *bindings = KeyBindings::from_names(&profile.key_bindings);
+        }
+        self.profile = Some(profile.name.clone());
+    }
+
+    // Writes the key bindings, and whatever the game sets in `update`, back to the active profile.
+    pub fn save_profile(&self, update: impl FnOnce(&mut Profile)) {
+        let Some(mut profile) = self.profile.as_deref().and_then(Profile::load_for_update) else { return };
+        if let Some(bindings) = self.key_bindings.as_ref().and_then(|b| b.lock().ok()) {
+            profile.key_bindings = bindings.to_names();
+        }
+        update(&mut profile);
+        profile.save();
+    }
+
+    // Adds the session that just ended to the active profile's history.
+    pub fn save_session(&self) {
+        let Some(report) = self.metrics.as_ref().and_then(|m| m.lock().ok()).map(|m| m.report()) else { return };
+        let Some(mut profile) = self.profile.as_deref().and_then(Profile::load_for_update) else { return };
+        profile.record_session(report);
+        profile.save();
+    }
+
+    // Adds to the current session's metrics, if they're being collected.
+    pub fn record(&self, f: impl FnOnce(&mut SessionMetrics)) {
+        if let Some(mut metrics) = self.metrics.as_ref().and_then(|m| m.lock().ok()) {
+            f(&mut metrics);
+        }
+    }
+
+    // Lets every active input source see the event, and on ticks collects what the threaded sources have queued.
+    // Each game's board calls this before handling the event itself.
+    pub fn route_input(&self, event: &mut dyn Event) {
+        if let (Some(inputs), Some(queue)) = (&self.inputs, &self.action_queue) {
+            if let Ok(mut inputs) = inputs.lock() {
+                inputs.event(event, queue);
+                if event.downcast_ref::<TickEvent>().is_some() {
+                    inputs.poll(queue);
+                }
+            }
+        }
+    }
+
+    // Everything the inputs have queued since the last call, oldest first.
+    pub fn take_actions(&self) -> Vec<GameAction> {
+        self.action_queue.as_ref().and_then(|q| q.lock().ok()).map(|mut q| q.drain(..).collect()).unwrap_or_default()
+    }
+}
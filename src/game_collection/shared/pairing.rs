@@ -0,0 +1,255 @@
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::fs;
+use std::net::IpAddr;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use serde::{Deserialize, Serialize};
+use rand::Rng;
+use log::{debug, info, warn};
+
+use crate::game_collection::shared::console::token_matches;
+
+pub type SharedPairing = Arc<Mutex<Pairing>>;
+
+// Digits in the pairing code shown in Settings.
+const CODE_LENGTH: usize = 6;
+// Random bytes in the secret a device is given when it pairs.
+const SECRET_BYTES: usize = 16;
+// Wrong codes before the code is replaced, so it can't be guessed by working through every number.
+const MAX_CODE_FAILURES: u32 = 3;
+// Longest an address has to wait after failed handshakes; the wait doubles with each failure up to this.
+const MAX_BACKOFF: Duration = Duration::from_secs(300);
+
+// Which controllers may drive the game. A new device has to send the code shown in Settings in its
+// handshake and is given a secret in return; it reconnects with that secret instead of a code until it's
+// kicked. Device ids are shown in Settings, so they're never enough on their own.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct Pairing {
+    // Device id to its secret.
+    allowed: BTreeMap<String, String>,
+    #[serde(skip)]
+    code: String,
+    // Goes up each time the code is replaced, so Settings can tell when the code it shows has gone stale.
+    #[serde(skip)]
+    generation: u64,
+    #[serde(skip)]
+    connected: BTreeSet<String>,
+    #[serde(skip)]
+    code_failures: u32,
+    // Failed handshakes per address, and when it may try again.
+    #[serde(skip)]
+    backoff: HashMap<IpAddr, (u32, Instant)>,
+    // Where the allow-list is saved; None keeps it in memory only.
+    #[serde(skip)]
+    file: Option<PathBuf>,
+}
+
+impl Pairing {
+    // The allow-list belongs to this machine rather than a player, so it sits beside the profiles folder.
+    fn path() -> PathBuf {
+        crate::logger::log_dir().with_file_name("controllers.json")
+    }
+
+    pub fn load() -> Pairing {
+        let mut pairing = fs::read_to_string(Self::path()).ok()
+            .and_then(|text| serde_json::from_str::<Pairing>(&text).inspect_err(|e| warn!(target: "server", "Ignoring saved controllers: {}", e)).ok())
+            .unwrap_or_default();
+        pairing.file = Some(Self::path());
+        pairing.new_code();
+        pairing
+    }
+
+    fn save(&self) {
+        let Some(path) = &self.file else { return };
+        let result = path.parent().map(fs::create_dir_all).unwrap_or(Ok(()))
+            .and_then(|_| serde_json::to_string_pretty(self).map_err(std::io::Error::other))
+            .and_then(|json| fs::write(path, json));
+        if let Err(e) = result {
+            warn!(target: "server", "Could not save allowed controllers: {}", e);
+        }
+    }
+
+    pub fn code(&self) -> &str {
+        &self.code
+    }
+
+    pub fn generation(&self) -> u64 {
+        self.generation
+    }
+
+    pub fn new_code(&mut self) {
+        let mut rng = rand::thread_rng();
+        self.code = (0..CODE_LENGTH).map(|_| char::from(b'0' + rng.gen_range(0..10u8))).collect();
+        self.code_failures = 0;
+        self.generation += 1;
+        debug!(target: "server", "Controller pairing code: {}", self.code);
+    }
+
+    // Checks a controller's opening message. Known devices need the secret they were given; anyone else needs
+    // the current code, which is then replaced so it can't be reused by someone who overheard it. Returns the
+    // new secret when the device has just paired. Addresses that keep failing are made to wait longer each time.
+    pub fn handshake(&mut self, peer: IpAddr, device: &str, code: Option<&str>, secret: Option<&str>) -> Result<Option<String>, &'static str> {
+        if self.backoff.get(&peer).is_some_and(|(_, until)| Instant::now() < *until) {
+            return Err("too many failed attempts, try again later");
+        }
+        let result = self.check(device, code, secret);
+        match result {
+            Ok(_) => { self.backoff.remove(&peer); }
+            Err(reason) => {
+                let failures = self.backoff.get(&peer).map(|(n, _)| n + 1).unwrap_or(1);
+                let wait = backoff_wait(failures);
+                self.backoff.insert(peer, (failures, Instant::now() + wait));
+                warn!(target: "server", "Refused controller {} from {}: {} ({} failures, next try in {}s)", device, peer, reason, failures, wait.as_secs());
+            }
+        }
+        result
+    }
+
+    fn check(&mut self, device: &str, code: Option<&str>, secret: Option<&str>) -> Result<Option<String>, &'static str> {
+        if device.is_empty() { return Err("missing device id"); }
+        let known = self.allowed.get(device).zip(secret).is_some_and(|(expected, secret)| token_matches(secret, expected));
+        let issued = match code {
+            _ if known => None,
+            Some(code) if token_matches(code, &self.code) => {
+                let secret = new_secret();
+                self.allowed.insert(device.to_string(), secret.clone());
+                self.save();
+                self.new_code();
+                info!(target: "server", "Paired controller {}", device);
+                Some(secret)
+            }
+            Some(_) => {
+                self.code_failures += 1;
+                if self.code_failures >= MAX_CODE_FAILURES {
+                    warn!(target: "server", "{} wrong pairing codes, replacing the code", self.code_failures);
+                    self.new_code();
+                }
+                return Err("wrong pairing code");
+            }
+            None if secret.is_some() => return Err("wrong device secret"),
+            None => return Err("pairing code required"),
+        };
+        self.connected.insert(device.to_string());
+        Ok(issued)
+    }
+
+    pub fn is_allowed(&self, device: &str) -> bool {
+        self.allowed.contains_key(device)
+    }
+
+    pub fn disconnected(&mut self, device: &str) {
+        self.connected.remove(device);
+    }
+
+    // Forgets a device; its connection is closed the next time the server checks it.
+    pub fn kick(&mut self, device: &str) {
+        if self.allowed.remove(device).is_some() {
+            self.save();
+            info!(target: "server", "Kicked controller {}", device);
+        }
+    }
+
+    // Every allowed device, and whether it's connected right now.
+    pub fn devices(&self) -> Vec<(String, bool)> {
+        self.allowed.keys().map(|d| (d.clone(), self.connected.contains(d))).collect()
+    }
+}
+
+// Doubles with each failure, starting at two seconds, up to MAX_BACKOFF.
+fn backoff_wait(failures: u32) -> Duration {
+    Duration::from_secs(1 << failures.min(16)).min(MAX_BACKOFF)
+}
+
+fn new_secret() -> String {
+    let mut rng = rand::thread_rng();
+    (0..SECRET_BYTES).map(|_| format!("{:02x}", rng.gen_range(0..=255u8))).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PEER: IpAddr = IpAddr::V4(std::net::Ipv4Addr::LOCALHOST);
+
+    // A pairing that never touches the disk, with a device already paired; returns its secret.
+    fn paired() -> (Pairing, String) {
+        let mut pairing = Pairing::default();
+        pairing.new_code();
+        let code = pairing.code().to_string();
+        let secret = pairing.handshake(PEER, "left-glove", Some(&code), None).unwrap().unwrap();
+        (pairing, secret)
+    }
+
+    #[test]
+    fn pairs_with_the_current_code_and_replaces_it() {
+        let mut pairing = Pairing::default();
+        pairing.new_code();
+        let (code, generation) = (pairing.code().to_string(), pairing.generation());
+        let secret = pairing.handshake(PEER, "left-glove", Some(&code), None).unwrap();
+        assert_eq!(secret.map(|s| s.len()), Some(SECRET_BYTES * 2));
+        assert!(pairing.is_allowed("left-glove"));
+        assert_eq!(pairing.generation(), generation + 1);
+        // The old code can't pair a second device.
+        assert_eq!(pairing.check("right-glove", Some(&code), None), Err("wrong pairing code"));
+    }
+
+    #[test]
+    fn known_device_needs_its_secret() {
+        let (mut pairing, secret) = paired();
+        assert_eq!(pairing.check("left-glove", None, Some(&secret)), Ok(None));
+        assert_eq!(pairing.check("left-glove", None, Some("0123456789abcdef")), Err("wrong device secret"));
+        assert_eq!(pairing.check("right-glove", None, Some(&secret)), Err("wrong device secret"));
+    }
+
+    #[test]
+    fn unknown_device_needs_a_code() {
+        let mut pairing = Pairing::default();
+        pairing.new_code();
+        assert_eq!(pairing.check("left-glove", None, None), Err("pairing code required"));
+        assert_eq!(pairing.check("", Some("123456"), None), Err("missing device id"));
+        assert!(!pairing.is_allowed("left-glove"));
+    }
+
+    #[test]
+    fn code_is_replaced_after_repeated_wrong_guesses() {
+        let mut pairing = Pairing::default();
+        pairing.new_code();
+        let generation = pairing.generation();
+        for _ in 1..MAX_CODE_FAILURES {
+            assert_eq!(pairing.check("left-glove", Some("wrong"), None), Err("wrong pairing code"));
+        }
+        assert_eq!(pairing.generation(), generation);
+        assert_eq!(pairing.check("left-glove", Some("wrong"), None), Err("wrong pairing code"));
+        assert_eq!(pairing.generation(), generation + 1);
+        assert_eq!(pairing.code_failures, 0);
+    }
+
+    #[test]
+    fn failed_handshakes_back_off() {
+        let mut pairing = Pairing::default();
+        pairing.new_code();
+        assert_eq!(pairing.handshake(PEER, "left-glove", None, None), Err("pairing code required"));
+        let code = pairing.code().to_string();
+        assert_eq!(pairing.handshake(PEER, "left-glove", Some(&code), None), Err("too many failed attempts, try again later"));
+        assert_eq!(pairing.backoff.get(&PEER).map(|(n, _)| *n), Some(1));
+        assert!(!pairing.is_allowed("left-glove"));
+    }
+
+    #[test]
+    fn backoff_doubles_up_to_the_cap() {
+        assert_eq!(backoff_wait(1), Duration::from_secs(2));
+        assert_eq!(backoff_wait(2), Duration::from_secs(4));
+        assert_eq!(backoff_wait(8), Duration::from_secs(256));
+        assert_eq!(backoff_wait(9), MAX_BACKOFF);
+        assert_eq!(backoff_wait(u32::MAX), MAX_BACKOFF);
+    }
+
+    #[test]
+    fn kicked_devices_are_refused() {
+        let (mut pairing, secret) = paired();
+        pairing.kick("left-glove");
+        assert!(!pairing.is_allowed("left-glove"));
+        assert_eq!(pairing.check("left-glove", None, Some(&secret)), Err("wrong device secret"));
+    }
+}
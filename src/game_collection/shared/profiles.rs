@@ -0,0 +1,167 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::PathBuf;
+use serde::{Deserialize, Serialize};
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+use log::{info, warn};
+
+use crate::game_collection::shared::keybindings::{Control, KeyBindings};
+use crate::game_collection::shared::metrics::SessionReport;
+
+// Everything that belongs to one player rather than to the device.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Profile {
+    pub name: String,
+    pub key_bindings: Vec<(Control, String)>,
+    // Highest sensor value seen per channel, so thresholds can be set against what this player can reach.
+    pub calibration: BTreeMap<String, f64>,
+    // Best scores per game, highest first.
+    pub high_scores: BTreeMap<String, Vec<u32>>,
+    pub sessions: Vec<SessionReport>,
+    // Each game's own settings by key, e.g. Galaga's "assists", kept at the top level of the file.
+    #[serde(flatten)]
+    pub settings: BTreeMap<String, Value>,
+}
+
+impl Profile {
+    const HIGH_SCORES: usize = 10;
+
+    pub fn new(name: &str) -> Self {
+        Profile {
+            name: name.to_string(),
+            key_bindings: KeyBindings::default().to_names(),
+            calibration: BTreeMap::new(),
+            high_scores: BTreeMap::new(),
+            sessions: Vec::new(),
+            settings: BTreeMap::new(),
+        }
+    }
+
+    // A game's setting, or None if it was never saved or no longer reads as `T`.
+    pub fn setting<T: DeserializeOwned>(&self, key: &str) -> Option<T> {
+        self.settings.get(key).and_then(|value| serde_json::from_value(value.clone()).ok())
+    }
+
+    pub fn set_setting<T: Serialize>(&mut self, key: &str, value: &T) {
+        match serde_json::to_value(value) {
+            Ok(value) => { self.settings.insert(key.to_string(), value); }
+            Err(e) => warn!(target: "galaga", "Could not store setting {}: {}", key, e),
+        }
+    }
+
+    // Profiles sit next to the logs and session reports under Documents.
+    pub fn dir() -> PathBuf {
+        crate::logger::log_dir().with_file_name("profiles")
+    }
+
+    fn path(name: &str) -> PathBuf {
+        let file = name.chars().map(|c| if c.is_ascii_alphanumeric() { c.to_ascii_lowercase() } else { '_' }).collect::<String>();
+        Self::dir().join(format!("{}.json", file))
+    }
+
+    // Every saved profile, with the last active one first.
+    pub fn load_all() -> Vec<Profile> {
+        let mut profiles = fs::read_dir(Self::dir()).into_iter().flatten().flatten()
+            .filter(|e| e.path().extension().is_some_and(|x| x == "json"))
+            .filter_map(|e| {
+                let text = fs::read_to_string(e.path()).ok()?;
+                serde_json::from_str::<Profile>(&text).inspect_err(|err| warn!(target: "galaga", "Skipping profile {}: {}", e.path().display(), err)).ok()
+            })
+            .collect::<Vec<_>>();
+        profiles.sort_by(|a, b| a.name.cmp(&b.name));
+        if let Some(i) = Self::active().and_then(|active| profiles.iter().position(|p| p.name == active)) {
+            let active = profiles.remove(i);
+            profiles.insert(0, active);
+        }
+        profiles
+    }
+
+    pub fn load(name: &str) -> Option<Profile> {
+        let text = fs::read_to_string(Self::path(name)).ok()?;
+        serde_json::from_str(&text).ok()
+    }
+
+    // The profile to change and save back: a new one if there's no file yet, or None if the file exists but
+    // can't be read, so it's left alone rather than overwritten with defaults.
+    pub fn load_for_update(name: &str) -> Option<Profile> {
+        let Ok(text) = fs::read_to_string(Self::path(name)) else { return Some(Profile::new(name)) };
+        serde_json::from_str(&text)
+            .inspect_err(|e| warn!(target: "galaga", "Not saving profile {}, its file can't be read: {}", name, e))
+            .ok()
+    }
+
+    pub fn save(&self) {
+        if let Err(e) = self.write() {
+            warn!(target: "galaga", "Could not save profile {}: {}", self.name, e);
+        }
+    }
+
+    fn write(&self) -> std::io::Result<()> {
+        fs::create_dir_all(Self::dir())
+            .and_then(|_| serde_json::to_string_pretty(self).map_err(std::io::Error::other))
+            .and_then(|json| fs::write(Self::path(&self.name), json))
+    }
+
+    // Whether a profile already has this name's file; names differing only in punctuation or case share one.
+    pub fn taken(name: &str) -> bool {
+        Self::path(name).exists()
+    }
+
+    // Moves the profile to a new name, keeping it active if it was. Refuses blank names and ones already in use.
+    pub fn rename(&mut self, name: &str) -> Result<(), String> {
+        let name = name.trim();
+        if name.is_empty() { return Err("name can't be blank".to_string()); }
+        if name == self.name { return Ok(()); }
+        if Self::path(name) != Self::path(&self.name) && Self::taken(name) { return Err(format!("{} is taken", name)); }
+
+        let old = std::mem::replace(&mut self.name, name.to_string());
+        if let Err(e) = self.write() {
+            self.name = old;
+            return Err(format!("could not save: {}", e));
+        }
+        if Self::path(&old) != Self::path(&self.name) {
+            let _ = fs::remove_file(Self::path(&old));
+        }
+        if Self::active().as_deref() == Some(old.as_str()) {
+            Self::set_active(&self.name);
+        }
+        info!(target: "galaga", "Renamed profile {} to {}", old, self.name);
+        Ok(())
+    }
+
+    // Name of the profile picked last time, remembered across launches.
+    pub fn active() -> Option<String> {
+        fs::read_to_string(Self::dir().join("active")).ok().map(|s| s.trim().to_string()).filter(|s| !s.is_empty())
+    }
+
+    pub fn set_active(name: &str) {
+        let result = fs::create_dir_all(Self::dir()).and_then(|_| fs::write(Self::dir().join("active"), name));
+        match result {
+            Ok(_) => info!(target: "galaga", "Active profile: {}", name),
+            Err(e) => warn!(target: "galaga", "Could not remember active profile: {}", e),
+        }
+    }
+
+    // First "Player N" name that isn't taken.
+    pub fn next_name(profiles: &[Profile]) -> String {
+        (1..).map(|n| format!("Player {}", n)).find(|name| profiles.iter().all(|p| p.name != *name)).unwrap()
+    }
+
+    // Adds a finished session to the history, high scores and calibration.
+    pub fn record_session(&mut self, report: SessionReport) {
+        let scores = self.high_scores.entry(report.game.clone()).or_default();
+        scores.push(report.score);
+        scores.sort_by(|a, b| b.cmp(a));
+        scores.truncate(Self::HIGH_SCORES);
+        for (channel, peak) in &report.peaks {
+            let best = self.calibration.entry(channel.clone()).or_insert(*peak);
+            *best = best.max(*peak);
+        }
+        self.sessions.push(report);
+    }
+
+    pub fn best_score(&self, game: &str) -> u32 {
+        self.high_scores.get(game).and_then(|s| s.first()).copied().unwrap_or(0)
+    }
+}
@@ -0,0 +1,289 @@
+use std::fmt;
+use serde::Deserialize;
+use serde_json::{json, Value};
+
+use crate::game_collection::shared::server::GameAction;
+
+// Version of the controller message format this build speaks. Messages without a `version` are from
+// sketches written before it was added and are read as version 1.
+pub const PROTOCOL_VERSION: u64 = 1;
+const ACTIONS: [&str; 4] = ["peakleft", "peakright", "peakshoot", "stop"];
+// Binary frames are an action byte, 1-4 in the order of ACTIONS, then the value as a big-endian u16.
+const FRAME_LENGTH: usize = 3;
+
+// How a controller sends its actions, announced in its hello. Binary suits microcontrollers that can't
+// afford to build JSON for every sensor sample.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Format {
+    #[default]
+    Json,
+    Binary,
+}
+
+impl Format {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Format::Json => "json",
+            Format::Binary => "binary",
+        }
+    }
+}
+
+// One controller message, e.g. `{"version":1,"action":"peakleft","value":620}`.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(tag = "action", rename_all = "lowercase", deny_unknown_fields)]
+pub enum ControllerMessage {
+    PeakLeft { value: f64 },
+    PeakRight { value: f64 },
+    PeakShoot { value: f64 },
+    Stop,
+}
+
+// Opening message of a connection that has to pair, e.g. `{"version":1,"hello":"left-glove","code":"123456","format":"binary"}`.
+// Devices that have paired before send the `secret` they were given instead of a code.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Hello {
+    pub hello: String,
+    #[serde(default)]
+    pub code: Option<String>,
+    #[serde(default)]
+    pub secret: Option<String>,
+    #[serde(default)]
+    pub format: Format,
+}
+
+// Why a message was rejected; sent back to the controller as a structured reply.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ProtocolError {
+    InvalidJson(String),
+    NotAnObject,
+    UnsupportedVersion(Value),
+    MissingAction,
+    UnknownAction(String),
+    InvalidFields(String),
+    InvalidFrame(String),
+    // Actions sent in the other format from the one the hello announced.
+    WrongFormat(Format),
+    // The connection isn't allowed to control the game: a failed pairing, or a kicked device.
+    Refused(String),
+}
+
+impl ProtocolError {
+    pub fn code(&self) -> &'static str {
+        match self {
+            ProtocolError::InvalidJson(_) => "invalid_json",
+            ProtocolError::NotAnObject => "not_an_object",
+            ProtocolError::UnsupportedVersion(_) => "unsupported_version",
+            ProtocolError::MissingAction => "missing_action",
+            ProtocolError::UnknownAction(_) => "unknown_action",
+            ProtocolError::InvalidFields(_) => "invalid_fields",
+            ProtocolError::InvalidFrame(_) => "invalid_frame",
+            ProtocolError::WrongFormat(_) => "wrong_format",
+            ProtocolError::Refused(_) => "refused",
+        }
+    }
+
+    // `{"version":1,"error":"unknown_action","message":"..."}`
+    pub fn reply(&self) -> String {
+        json!({"version": PROTOCOL_VERSION, "error": self.code(), "message": self.to_string()}).to_string()
+    }
+}
+
+impl fmt::Display for ProtocolError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ProtocolError::InvalidJson(e) => write!(f, "not valid JSON: {}", e),
+            ProtocolError::NotAnObject => write!(f, "messages must be JSON objects"),
+            ProtocolError::UnsupportedVersion(v) => write!(f, "protocol version {} is not supported, this game speaks {}", v, PROTOCOL_VERSION),
+            ProtocolError::MissingAction => write!(f, "missing \"action\""),
+            ProtocolError::UnknownAction(a) => write!(f, "unknown action \"{}\", expected one of {}", a, ACTIONS.join(", ")),
+            ProtocolError::InvalidFields(e) => write!(f, "{}", e),
+            ProtocolError::InvalidFrame(e) => write!(f, "{}", e),
+            ProtocolError::WrongFormat(format) => write!(f, "this connection announced {} messages", format.name()),
+            ProtocolError::Refused(reason) => write!(f, "{}", reason),
+        }
+    }
+}
+
+impl ControllerMessage {
+    pub fn parse(text: &str) -> Result<ControllerMessage, ProtocolError> {
+        let json = versioned(text)?;
+        let action = json.get("action").ok_or(ProtocolError::MissingAction)?;
+        let Some(name) = action.as_str().filter(|a| ACTIONS.contains(a)) else {
+            return Err(ProtocolError::UnknownAction(action.as_str().map(str::to_string).unwrap_or_else(|| action.to_string())));
+        };
+        serde_json::from_value(json.clone()).map_err(|e| ProtocolError::InvalidFields(format!("{}: {}", name, e)))
+    }
+
+    pub fn from_frame(frame: &[u8]) -> Result<ControllerMessage, ProtocolError> {
+        let [action, high, low] = frame else {
+            return Err(ProtocolError::InvalidFrame(format!("frames are {} bytes, got {}", FRAME_LENGTH, frame.len())));
+        };
+        let value = u16::from_be_bytes([*high, *low]) as f64;
+        match action {
+            1 => Ok(ControllerMessage::PeakLeft { value }),
+            2 => Ok(ControllerMessage::PeakRight { value }),
+            3 => Ok(ControllerMessage::PeakShoot { value }),
+            4 => Ok(ControllerMessage::Stop),
+            _ => Err(ProtocolError::InvalidFrame(format!("unknown action byte {}", action))),
+        }
+    }
+
+    // Inverse of `from_frame`, for building frames in tests. Values are clamped to the u16 range; stop is sent with a zero value.
+    #[cfg(test)]
+    pub fn to_frame(&self) -> [u8; FRAME_LENGTH] {
+        let (action, value) = match self {
+            ControllerMessage::PeakLeft { value } => (1, *value),
+            ControllerMessage::PeakRight { value } => (2, *value),
+            ControllerMessage::PeakShoot { value } => (3, *value),
+            ControllerMessage::Stop => (4, 0.0),
+        };
+        let [high, low] = (value.clamp(0.0, u16::MAX as f64) as u16).to_be_bytes();
+        [action, high, low]
+    }
+
+    // The action to queue and the sensor reading that triggered it.
+    pub fn action(&self) -> (GameAction, Option<f64>) {
+        match self {
+            ControllerMessage::PeakLeft { value } => (GameAction::MoveLeft, Some(*value)),
+            ControllerMessage::PeakRight { value } => (GameAction::MoveRight, Some(*value)),
+            ControllerMessage::PeakShoot { value } => (GameAction::Shoot, Some(*value)),
+            ControllerMessage::Stop => (GameAction::StopMoving, None),
+        }
+    }
+}
+
+impl Hello {
+    pub fn parse(text: &str) -> Result<Hello, ProtocolError> {
+        let json = versioned(text)?;
+        serde_json::from_value(json).map_err(|e| ProtocolError::InvalidFields(format!("hello: {}", e)))
+    }
+}
+
+// Parses a message and checks its version, returning the object without the version field.
+fn versioned(text: &str) -> Result<Value, ProtocolError> {
+    let mut json = serde_json::from_str::<Value>(text).map_err(|e| ProtocolError::InvalidJson(e.to_string()))?;
+    let object = json.as_object_mut().ok_or(ProtocolError::NotAnObject)?;
+    match object.remove("version") {
+        None => Ok(json),
+        Some(v) if v.as_u64() == Some(PROTOCOL_VERSION) => Ok(json),
+        Some(v) => Err(ProtocolError::UnsupportedVersion(v)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_peak_actions() {
+        let cases = [
+            (r#"{"version":1,"action":"peakleft","value":620}"#, ControllerMessage::PeakLeft { value: 620.0 }, GameAction::MoveLeft, 620.0),
+            (r#"{"version":1,"action":"peakright","value":540.5}"#, ControllerMessage::PeakRight { value: 540.5 }, GameAction::MoveRight, 540.5),
+            (r#"{"version":1,"action":"peakshoot","value":700}"#, ControllerMessage::PeakShoot { value: 700.0 }, GameAction::Shoot, 700.0),
+        ];
+        for (json, expected, action, value) in cases {
+            let message = ControllerMessage::parse(json).unwrap();
+            assert_eq!(message, expected, "{}", json);
+            assert_eq!(message.action(), (action, Some(value)), "{}", json);
+        }
+    }
+
+    #[test]
+    fn parses_stop() {
+        let message = ControllerMessage::parse(r#"{"version":1,"action":"stop"}"#).unwrap();
+        assert_eq!(message, ControllerMessage::Stop);
+        assert_eq!(message.action(), (GameAction::StopMoving, None));
+    }
+
+    #[test]
+    fn reads_unversioned_messages_as_version_1() {
+        assert_eq!(ControllerMessage::parse(r#"{"action":"stop"}"#), Ok(ControllerMessage::Stop));
+        assert_eq!(ControllerMessage::parse(r#"{"action":"peakleft","value":1}"#), Ok(ControllerMessage::PeakLeft { value: 1.0 }));
+    }
+
+    #[test]
+    fn parses_hello() {
+        let hello = Hello::parse(r#"{"version":1,"hello":"left-glove","code":"123456"}"#).unwrap();
+        assert_eq!(hello, Hello { hello: "left-glove".to_string(), code: Some("123456".to_string()), secret: None, format: Format::Json });
+        assert_eq!(Hello::parse(r#"{"hello":"left-glove"}"#).unwrap().code, None);
+    }
+
+    #[test]
+    fn hello_announces_format() {
+        assert_eq!(Hello::parse(r#"{"hello":"left-glove"}"#).unwrap().format, Format::Json);
+        assert_eq!(Hello::parse(r#"{"hello":"left-glove","format":"json"}"#).unwrap().format, Format::Json);
+        assert_eq!(Hello::parse(r#"{"hello":"left-glove","format":"binary"}"#).unwrap().format, Format::Binary);
+        assert!(matches!(Hello::parse(r#"{"hello":"left-glove","format":"morse"}"#), Err(ProtocolError::InvalidFields(_))));
+    }
+
+    #[test]
+    fn parses_binary_frames() {
+        assert_eq!(ControllerMessage::from_frame(&[1, 0x02, 0x6c]), Ok(ControllerMessage::PeakLeft { value: 620.0 }));
+        assert_eq!(ControllerMessage::from_frame(&[2, 0x02, 0x1c]), Ok(ControllerMessage::PeakRight { value: 540.0 }));
+        assert_eq!(ControllerMessage::from_frame(&[3, 0xff, 0xff]), Ok(ControllerMessage::PeakShoot { value: 65535.0 }));
+        assert_eq!(ControllerMessage::from_frame(&[4, 0, 0]), Ok(ControllerMessage::Stop));
+    }
+
+    #[test]
+    fn frames_round_trip() {
+        let messages = [
+            ControllerMessage::PeakLeft { value: 620.0 },
+            ControllerMessage::PeakRight { value: 0.0 },
+            ControllerMessage::PeakShoot { value: 1023.0 },
+            ControllerMessage::Stop,
+        ];
+        for message in messages {
+            assert_eq!(ControllerMessage::from_frame(&message.to_frame()), Ok(message.clone()));
+        }
+        assert_eq!(ControllerMessage::PeakLeft { value: 70000.0 }.to_frame(), [1, 0xff, 0xff]);
+    }
+
+    #[test]
+    fn rejects_bad_frames() {
+        assert!(matches!(ControllerMessage::from_frame(&[1, 2]), Err(ProtocolError::InvalidFrame(_))));
+        assert!(matches!(ControllerMessage::from_frame(&[1, 2, 3, 4]), Err(ProtocolError::InvalidFrame(_))));
+        assert!(matches!(ControllerMessage::from_frame(&[]), Err(ProtocolError::InvalidFrame(_))));
+        assert!(matches!(ControllerMessage::from_frame(&[9, 0, 0]), Err(ProtocolError::InvalidFrame(_))));
+    }
+
+    #[test]
+    fn rejects_invalid_json() {
+        assert!(matches!(ControllerMessage::parse("{\"action\":"), Err(ProtocolError::InvalidJson(_))));
+        assert_eq!(ControllerMessage::parse("[1, 2]"), Err(ProtocolError::NotAnObject));
+    }
+
+    #[test]
+    fn rejects_other_versions() {
+        let error = ControllerMessage::parse(r#"{"version":2,"action":"stop"}"#).unwrap_err();
+        assert_eq!(error, ProtocolError::UnsupportedVersion(json!(2)));
+        assert!(matches!(ControllerMessage::parse(r#"{"version":"1","action":"stop"}"#), Err(ProtocolError::UnsupportedVersion(_))));
+    }
+
+    #[test]
+    fn rejects_missing_and_unknown_actions() {
+        assert_eq!(ControllerMessage::parse(r#"{"value":3}"#), Err(ProtocolError::MissingAction));
+        assert_eq!(ControllerMessage::parse(r#"{"action":"jump"}"#), Err(ProtocolError::UnknownAction("jump".to_string())));
+        assert_eq!(ControllerMessage::parse(r#"{"action":7}"#), Err(ProtocolError::UnknownAction("7".to_string())));
+    }
+
+    #[test]
+    fn rejects_missing_or_mistyped_values() {
+        assert!(matches!(ControllerMessage::parse(r#"{"action":"peakleft"}"#), Err(ProtocolError::InvalidFields(_))));
+        assert!(matches!(ControllerMessage::parse(r#"{"action":"peakright","value":"high"}"#), Err(ProtocolError::InvalidFields(_))));
+        assert!(matches!(ControllerMessage::parse(r#"{"action":"peakshoot","value":700,"extra":1}"#), Err(ProtocolError::InvalidFields(_))));
+        assert!(matches!(Hello::parse(r#"{"code":"123456"}"#), Err(ProtocolError::InvalidFields(_))));
+    }
+
+    #[test]
+    fn error_replies_are_structured() {
+        let reply = ControllerMessage::parse(r#"{"action":"jump"}"#).unwrap_err().reply();
+        let reply = serde_json::from_str::<Value>(&reply).unwrap();
+        assert_eq!(reply["version"], json!(PROTOCOL_VERSION));
+        assert_eq!(reply["error"], json!("unknown_action"));
+        assert!(reply["message"].as_str().unwrap().contains("jump"));
+        assert_eq!(ProtocolError::WrongFormat(Format::Binary).code(), "wrong_format");
+    }
+}
@@ -0,0 +1,103 @@
+use pelican_ui::events::{Event, OnEvent};
+use pelican_ui::drawable::{Drawable, Component};
+use pelican_ui::layout::{Area, SizeRequest, Layout};
+use pelican_ui::{Context, Component};
+use pelican_ui_std::{Stack, Content, Header, Page, Offset, AppPage, IconButton, NavigateEvent, DataItem};
+use pelican_game_engine::Gameboard;
+use log::{info, warn};
+
+use crate::game_collection::shared::events::ExportSession;
+use crate::game_collection::shared::metrics::{ExportFormat, SessionMetrics, SessionReport};
+use crate::game_collection::shared::components::DataItemSettings;
+use crate::game_collection::shared::SharedState;
+
+// Position of the export item, after the summary rows.
+const EXPORT_INDEX: usize = 6;
+
+// Builds the page that playing again goes back to, from the game's own gameboard.
+pub type Replay = fn(&mut Context, Gameboard) -> Box<dyn AppPage>;
+
+// Shown at game over with the metrics of the session that just ended. Any game that records into the shared
+// session metrics can show it.
+#[derive(Debug, Component)]
+pub struct ReportPage(Stack, Page, #[skip] Option<Gameboard>, #[skip] Option<SessionReport>, #[skip] Replay);
+
+impl OnEvent for ReportPage {
+    fn on_event(&mut self, _ctx: &mut Context, event: &mut dyn Event) -> bool {
+        if let Some(ExportSession(format)) = event.downcast_ref::<ExportSession>() {
+            let Some(report) = &self.3 else { return true };
+            let label = match report.export(*format) {
+                Ok(path) => {
+                    info!(target: "galaga", "Session report saved to {}", path.display());
+                    format!("Saved: {}", path.display())
+                }
+                Err(e) => {
+                    warn!(target: "galaga", "Could not save session report: {}", e);
+                    format!("Export failed: {}", e)
+                }
+            };
+            *self.1.content().find_at::<DataItem>(EXPORT_INDEX).unwrap().label() = label;
+        }
+        true
+    }
+}
+
+impl AppPage for ReportPage {
+    fn has_nav(&self) -> bool {false}
+    fn navigate(mut self: Box<Self>, ctx: &mut Context, index: usize) -> Result<Box<dyn AppPage>, Box<dyn AppPage>> {
+        match index {
+            // Playing again starts a fresh session.
+            0 => {
+                ctx.state().get_mut_or_default::<SharedState>().record(|m| *m = SessionMetrics::new(m.game()));
+                Ok((self.4)(ctx, self.2.take().unwrap()))
+            }
+            _ => Err(self)
+        }
+    }
+}
+
+impl ReportPage {
+    pub fn new(ctx: &mut Context, gameboard: Gameboard, replay: Replay) -> Self {
+        let report = {
+            let shared = ctx.state().get_mut_or_default::<SharedState>();
+            shared.metrics.as_ref().and_then(|m| m.lock().ok()).map(|m| m.report())
+        };
+
+        let rows = match &report {
+            Some(report) => {
+                let minutes = report.session_secs as u32 / 60;
+                let seconds = report.session_secs as u32 % 60;
+                let reaction = match report.mean_reaction_ms {
+                    Some(ms) => format!("Average Reaction Time: {}ms", ms),
+                    None => "Average Reaction Time: -".to_string(),
+                };
+                let actions = report.actions.iter().map(|(c, n)| format!("{} {}", c, n)).collect::<Vec<_>>().join(", ");
+                let peaks = report.peaks.iter().map(|(c, p)| format!("{} {:.0}", c, p)).collect::<Vec<_>>().join(", ");
+                vec![
+                    (format!("Session Length: {}:{:02}", minutes, seconds), "Time from the first tick to game over.".to_string()),
+                    (format!("Score: {}", report.score), format!("{} lives lost.", report.lives_lost)),
+                    (format!("Accuracy: {:.0}%", report.accuracy * 100.0), format!("{} hits from {} shots.", report.hits, report.shots)),
+                    (reaction, format!("Time from an enemy shot at the ship to a move, over {} dodges.", report.dodges)),
+                    ("Actions".to_string(), if actions.is_empty() { "None".to_string() } else { actions }),
+                    ("Peak Sensor Values".to_string(), if peaks.is_empty() { "None".to_string() } else { peaks }),
+                ]
+            }
+            None => vec![("No session data".to_string(), String::new()); EXPORT_INDEX],
+        };
+
+        let mut items = rows.into_iter()
+            .map(|(title, sub)| DataItem::new(ctx, None, &title, Some(&sub), None, None, None))
+            .collect::<Vec<_>>();
+        items.push(DataItemSettings::new(ctx, "Export Session", "Saves these metrics as a CSV or JSON file.", vec![
+            ("add", "CSV", Box::new(|ctx: &mut Context| ctx.trigger_event(ExportSession(ExportFormat::Csv))) as Box<dyn FnMut(&mut Context)>),
+            ("add", "JSON", Box::new(|ctx: &mut Context| ctx.trigger_event(ExportSession(ExportFormat::Json))) as Box<dyn FnMut(&mut Context)>),
+        ]));
+
+        let back = IconButton::navigation(ctx, "left", |ctx: &mut Context| ctx.trigger_event(NavigateEvent(0)));
+        let header = Header::stack(ctx, Some(back), "Session Report", None);
+        let drawable_items: Vec<Box<dyn Drawable>> = items.into_iter().map(|item| Box::new(item) as Box<dyn Drawable>).collect();
+        let content = Content::new(Offset::Start, drawable_items);
+
+        ReportPage(Stack::default(), Page::new(Some(header), content, None), Some(gameboard), report, replay)
+    }
+}
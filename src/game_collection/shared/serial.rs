@@ -0,0 +1,145 @@
+use std::io::{BufRead, BufReader, ErrorKind};
+use std::thread::{sleep, spawn, JoinHandle};
+use std::sync::{Arc, Mutex};
+use std::collections::VecDeque;
+use std::time::Duration;
+use log::{debug, info, warn};
+
+use crate::game_collection::shared::server::{ArduinoServer, GameAction};
+use crate::game_collection::shared::metrics::SharedMetrics;
+
+pub const BAUD_RATES: [u32; 5] = [9600, 19200, 38400, 57600, 115200];
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SerialConfig {
+    pub path: String,
+    pub baud: u32,
+}
+
+impl Default for SerialConfig {
+    fn default() -> Self {
+        SerialConfig { path: "/dev/ttyACM0".to_string(), baud: 9600 }
+    }
+}
+
+impl SerialConfig {
+    // Device paths currently present on the system, e.g. /dev/ttyACM0 or /dev/cu.usbmodem1101.
+    pub fn available_paths() -> Vec<String> {
+        serialport::available_ports().map(|ports| ports.into_iter().map(|p| p.port_name).collect()).unwrap_or_default()
+    }
+}
+
+// Reads the same JSON lines the WebSocket controllers send, but over a USB CDC serial port.
+pub struct SerialReader {
+    config: Arc<Mutex<SerialConfig>>,
+    action_queue: Arc<Mutex<VecDeque<GameAction>>>,
+    metrics: Option<SharedMetrics>,
+}
+
+impl SerialReader {
+    const RETRY: Duration = Duration::from_secs(2);
+    const READ_TIMEOUT: Duration = Duration::from_millis(100);
+
+    pub fn new(config: Arc<Mutex<SerialConfig>>) -> Self {
+        SerialReader {
+            config,
+            action_queue: Arc::new(Mutex::new(VecDeque::new())),
+            metrics: None,
+        }
+    }
+
+    pub fn set_metrics(&mut self, metrics: SharedMetrics) {
+        self.metrics = Some(metrics);
+    }
+
+    pub fn get_action_queue(&self) -> Arc<Mutex<VecDeque<GameAction>>> {
+        self.action_queue.clone()
+    }
+
+    // Keeps (re)opening the configured port; a changed path or baud rate closes the current port and opens the new one.
+    pub fn start(&self) -> JoinHandle<()> {
+        let config = self.config.clone();
+        let action_queue = self.action_queue.clone();
+        let metrics = self.metrics.clone();
+
+        spawn(move || loop {
+            let wanted = match config.lock() {
+                Ok(config) => config.clone(),
+                Err(_) => return,
+            };
+            match serialport::new(&wanted.path, wanted.baud).timeout(Self::READ_TIMEOUT).open() {
+                Ok(port) => {
+                    info!(target: "server", "Serial port {} opened at {} baud", wanted.path, wanted.baud);
+                    Self::read_lines(BufReader::new(port), &action_queue, &metrics, &config, &wanted);
+                    info!(target: "server", "Serial port {} closed", wanted.path);
+                    // A device that hung up or errored is retried after a pause; a changed config reopens right away.
+                    if config.lock().is_ok_and(|c| *c == wanted) {
+                        sleep(Self::RETRY);
+                    }
+                }
+                Err(e) => {
+                    debug!(target: "server", "Serial port {} unavailable: {}", wanted.path, e);
+                    sleep(Self::RETRY);
+                }
+            }
+        })
+    }
+
+    fn read_lines(mut reader: impl BufRead, action_queue: &Arc<Mutex<VecDeque<GameAction>>>, metrics: &Option<SharedMetrics>, config: &Arc<Mutex<SerialConfig>>, wanted: &SerialConfig) {
+        // Bytes are kept across read timeouts so a line split over two reads still parses.
+        let mut line = Vec::new();
+        loop {
+            if config.lock().map(|c| *c != *wanted).unwrap_or(true) {
+                return;
+            }
+            match reader.read_until(b'\n', &mut line) {
+                Ok(0) => return,
+                Ok(_) if line.ends_with(b"\n") => {
+                    let text = String::from_utf8_lossy(&line);
+                    let text = text.trim();
+                    debug!(target: "server", "Serial received: {}", text);
+                    match ArduinoServer::parse_action(text) {
+                        Ok(parsed) => ArduinoServer::push_action(action_queue, metrics, parsed),
+                        Err(error) => warn!(target: "server", "Rejected serial message {}: {}", text, error),
+                    }
+                    line.clear();
+                }
+                Ok(_) => {}
+                Err(e) if matches!(e.kind(), ErrorKind::TimedOut | ErrorKind::WouldBlock | ErrorKind::Interrupted) => {}
+                Err(e) => {
+                    warn!(target: "server", "Serial error on {}: {}", wanted.path, e);
+                    return;
+                }
+            }
+        }
+    }
+}
+
+#[cfg(all(test, target_os = "linux"))]
+mod tests {
+    use super::*;
+    use serialport::{SerialPort, TTYPort};
+    use std::io::Write;
+    use std::time::Instant;
+
+    #[test]
+    fn reads_json_lines_from_pty() {
+        let (mut master, slave) = TTYPort::pair().expect("pseudo-terminal pair");
+        let path = slave.name().expect("slave device path");
+        let reader = SerialReader::new(Arc::new(Mutex::new(SerialConfig { path, baud: 9600 })));
+        let queue = reader.get_action_queue();
+        let _handle = reader.start();
+        sleep(Duration::from_millis(300));
+
+        master.write_all(b"{\"action\":\"peakleft\",\"value\":620}\n{\"action\":\"peakshoot\",").unwrap();
+        sleep(Duration::from_millis(250));
+        master.write_all(b"\"value\":700}\n{\"action\":\"stop\"}\n").unwrap();
+
+        let start = Instant::now();
+        while queue.lock().unwrap().len() < 3 && start.elapsed() < Duration::from_secs(3) {
+            sleep(Duration::from_millis(20));
+        }
+        let actions = queue.lock().unwrap().drain(..).collect::<Vec<_>>();
+        assert!(matches!(actions.as_slice(), [GameAction::MoveLeft, GameAction::Shoot, GameAction::StopMoving]), "{:?}", actions);
+    }
+}
@@ -0,0 +1,421 @@
+use std::net::TcpListener;
+use std::thread::{spawn, JoinHandle};
+use tungstenite::{accept, Message, WebSocket};
+use tungstenite::protocol::WebSocket as WS;
+use std::net::{IpAddr, TcpStream};
+use serde_json::{json, Value};
+use local_ip_address::local_ip;
+use std::sync::{mpsc, Arc, Mutex};
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+use log::{debug, error, info, trace, warn};
+
+use crate::game_collection::shared::metrics::SharedMetrics;
+use crate::game_collection::shared::console::{self, ConsoleQueue};
+use crate::game_collection::shared::pairing::SharedPairing;
+use crate::game_collection::shared::protocol::{ControllerMessage, Format, Hello, ProtocolError, PROTOCOL_VERSION};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GameAction {
+    MoveLeft,
+    MoveRight,
+    Shoot,
+    StopMoving,
+    Pause,
+    OpenSettings,
+}
+
+impl GameAction {
+    // Name used for this action in session metrics; matches the protocol's action names where there is one.
+    pub fn channel(&self) -> &'static str {
+        match self {
+            GameAction::MoveLeft => "peakleft",
+            GameAction::MoveRight => "peakright",
+            GameAction::Shoot => "peakshoot",
+            GameAction::StopMoving => "stop",
+            GameAction::Pause => "pause",
+            GameAction::OpenSettings => "settings",
+        }
+    }
+}
+
+pub struct ArduinoServer {
+    ip: String,
+    port: u16,
+    action_queue: Arc<Mutex<VecDeque<GameAction>>>,
+    metrics: Option<SharedMetrics>,
+    console: Option<ConsoleQueue>,
+    pairing: Option<SharedPairing>,
+}
+
+impl ArduinoServer {
+    // How long a read waits before the connection checks for console acknowledgements to send.
+    const READ_TIMEOUT: Duration = Duration::from_millis(50);
+
+    pub fn new(port: u16) -> Self {
+        let local_ip = local_ip().unwrap();
+        ArduinoServer {
+            ip: local_ip.to_string(),
+            port,
+            action_queue: Arc::new(Mutex::new(VecDeque::new())),
+            metrics: None,
+            console: None,
+            pairing: None,
+        }
+    }
+
+    pub fn set_metrics(&mut self, metrics: SharedMetrics) {
+        self.metrics = Some(metrics);
+    }
+
+    pub fn set_console(&mut self, console: ConsoleQueue) {
+        self.console = Some(console);
+    }
+
+    // Once set, every connection has to pair before anything it sends is acted on.
+    pub fn set_pairing(&mut self, pairing: SharedPairing) {
+        self.pairing = Some(pairing);
+    }
+
+    pub fn get_action_queue(&self) -> Arc<Mutex<VecDeque<GameAction>>> {
+        self.action_queue.clone()
+    }
+
+    pub fn start(&self) -> JoinHandle<()> {
+        let ip = self.ip.clone();
+        let port = self.port;
+        let action_queue = self.action_queue.clone();
+        let metrics = self.metrics.clone();
+        let console = self.console.clone();
+        let pairing = self.pairing.clone();
+        
+        spawn(move || {
+            let bind_address = format!("{}:{}", ip, port);
+            
+            let server = TcpListener::bind(&bind_address).unwrap();
+            info!(target: "server", "WebSocket server listening on {}", bind_address);
+            info!(target: "server", "Connect your Arduino to: {}", ip);
+
+            // Set non-blocking mode for the server
+            server.set_nonblocking(true).unwrap();
+            
+            let mut last_status_print = Instant::now();
+            let status_interval = Duration::from_secs(2); // Log every 2 seconds
+            
+            loop {
+                // Log status periodically
+                if last_status_print.elapsed() >= status_interval {
+                    trace!(target: "server", "Server running - listening for connections on {}", bind_address);
+                    last_status_print = Instant::now();
+                }
+                
+                // Check for incoming connections
+                match server.accept() {
+                    Ok((stream, _)) => {
+                        let queue = action_queue.clone();
+                        let metrics = metrics.clone();
+                        let console = console.clone();
+                        let pairing = pairing.clone();
+                        spawn(move || {
+                            Self::handle_client(stream, queue, metrics, console, pairing);
+                        });
+                    }
+                    Err(ref e) if e.kind() == std::io::ErrorKind::WouldBlock => {
+                        // No connection available, sleep briefly and continue
+                        std::thread::sleep(Duration::from_millis(100));
+                    }
+                    Err(e) => {
+                        error!(target: "server", "Error accepting connection: {}", e);
+                    }
+                }
+            }
+        })
+    }
+
+    fn handle_client(stream: TcpStream, action_queue: Arc<Mutex<VecDeque<GameAction>>>, metrics: Option<SharedMetrics>, console: Option<ConsoleQueue>, pairing: Option<SharedPairing>) {
+        let Ok(peer) = stream.peer_addr().map(|a| a.ip()) else { return };
+        let mut websocket = match accept(stream) {
+            Ok(websocket) => websocket,
+            Err(e) => {
+                warn!(target: "server", "WebSocket handshake failed: {}", e);
+                return;
+            }
+        };
+        // Reads time out so console acknowledgements and kicks are picked up between messages. Set only after
+        // the handshake, which slow clients can take longer than this over.
+        websocket.get_ref().set_read_timeout(Some(Self::READ_TIMEOUT)).ok();
+        info!(target: "server", "New WebSocket connection established");
+        let (ack_sender, acks) = mpsc::channel::<String>();
+        let mut authorised = false;
+        // Set by the handshake; without pairing every connection is trusted.
+        let mut device: Option<String> = None;
+        let mut paired = pairing.is_none();
+        // Announced in the hello; connections that don't send one speak JSON.
+        let mut format = Format::Json;
+
+        loop {
+            if let (Some(pairing), Some(id)) = (&pairing, &device) {
+                if !pairing.lock().map(|p| p.is_allowed(id)).unwrap_or(false) {
+                    info!(target: "server", "Closing connection from kicked controller {}", id);
+                    let reply = ProtocolError::Refused("removed from allowed controllers".to_string()).reply();
+                    websocket.send(Message::text(reply)).ok();
+                    websocket.close(None).ok();
+                    break;
+                }
+            }
+            while let Ok(ack) = acks.try_recv() {
+                if let Err(e) = websocket.send(Message::text(ack)) {
+                    warn!(target: "server", "Couldn't send console acknowledgement: {}", e);
+                }
+            }
+            match websocket.read() {
+                Ok(Message::Binary(frame)) => {
+                    trace!(target: "server", "Received frame: {:?}", frame);
+                    if !paired {
+                        websocket.send(Message::text(ProtocolError::Refused("send a hello first".to_string()).reply())).ok();
+                        websocket.close(None).ok();
+                        break;
+                    }
+                    let parsed = match format {
+                        Format::Binary => ControllerMessage::from_frame(&frame).map(|m| m.action()),
+                        Format::Json => Err(ProtocolError::WrongFormat(format)),
+                    };
+                    match parsed {
+                        Ok(parsed) => Self::push_action(&action_queue, &metrics, parsed),
+                        Err(error) => {
+                            warn!(target: "server", "Rejected frame {:?}: {}", frame, error);
+                            websocket.send(Message::text(error.reply())).ok();
+                        }
+                    }
+                }
+                Ok(msg) => {
+                    if msg.is_text() {
+                        let text = msg.to_text().unwrap();
+                        debug!(target: "server", "Received: {}", text);
+                        let json = serde_json::from_str::<Value>(text).ok();
+                        if !paired {
+                            let Some(pairing) = &pairing else { break };
+                            match Self::handshake(text, peer, pairing) {
+                                Ok((hello, secret)) => {
+                                    let mut reply = json!({"version": PROTOCOL_VERSION, "paired": true, "device": hello.hello, "format": hello.format.name()});
+                                    // Only sent once, when the device first pairs; it has to keep it to reconnect.
+                                    if let Some(secret) = secret { reply["secret"] = json!(secret); }
+                                    let reply = reply.to_string();
+                                    websocket.send(Message::text(reply)).ok();
+                                    debug!(target: "server", "Controller {} sends {} messages", hello.hello, hello.format.name());
+                                    device = Some(hello.hello);
+                                    format = hello.format;
+                                    paired = true;
+                                }
+                                Err(error) => {
+                                    debug!(target: "server", "Handshake failed: {}", error);
+                                    websocket.send(Message::text(error.reply())).ok();
+                                    websocket.close(None).ok();
+                                    break;
+                                }
+                            }
+                        } else if let Some(json) = json.filter(|j| j.get("console").is_some()) {
+                            let reply = Self::console_message(&json, &mut authorised, &console, &ack_sender);
+                            if let Some(reply) = reply {
+                                websocket.send(Message::text(reply)).ok();
+                            }
+                        } else if format == Format::Binary {
+                            let error = ProtocolError::WrongFormat(format);
+                            warn!(target: "server", "Rejected message {}: {}", text, error);
+                            websocket.send(Message::text(error.reply())).ok();
+                        } else {
+                            match Self::parse_action(text) {
+                                Ok(parsed) => Self::push_action(&action_queue, &metrics, parsed),
+                                Err(error) => {
+                                    warn!(target: "server", "Rejected message {}: {}", text, error);
+                                    websocket.send(Message::text(error.reply())).ok();
+                                }
+                            }
+                        }
+                    }
+                }
+                Err(tungstenite::Error::Io(e)) if matches!(e.kind(), std::io::ErrorKind::WouldBlock | std::io::ErrorKind::TimedOut) => {}
+                Err(e) => {
+                    warn!(target: "server", "WebSocket error: {}", e);
+                    break;
+                }
+            }
+        }
+
+        if let (Some(pairing), Some(id)) = (&pairing, &device) {
+            if let Ok(mut pairing) = pairing.lock() { pairing.disconnected(id); }
+        }
+        info!(target: "server", "Client disconnected");
+    }
+
+    // A controller's first message has to be a `protocol::Hello`, which also picks JSON or binary frames for the
+    // rest of the connection. Devices that have paired before send their secret instead of the code.
+    fn handshake(text: &str, peer: IpAddr, pairing: &SharedPairing) -> Result<(Hello, Option<String>), ProtocolError> {
+        let hello = Hello::parse(text)?;
+        let refused = |reason: &str| ProtocolError::Refused(reason.to_string());
+        let mut pairing = pairing.lock().map_err(|_| refused("pairing unavailable"))?;
+        let secret = pairing.handshake(peer, &hello.hello, hello.code.as_deref(), hello.secret.as_deref()).map_err(refused)?;
+        Ok((hello, secret))
+    }
+
+    // Handles a dev console message. Commands only go through once this connection has sent the console token;
+    // anything that can't be queued is answered straight away, queued commands are checked and acknowledged by the game.
+    fn console_message(json: &Value, authorised: &mut bool, console: &Option<ConsoleQueue>, ack_sender: &mpsc::Sender<String>) -> Option<String> {
+        let name = json.get("console").and_then(|c| c.as_str()).unwrap_or_default();
+        let Some(queue) = console.as_ref() else { return Some(console::ack(name, &Err("console not available".to_string()))) };
+        let Some(token) = console::token() else { return Some(console::ack(name, &Err("console is disabled".to_string()))) };
+
+        if name == "auth" {
+            let given = json.get("token").and_then(|t| t.as_str()).unwrap_or_default();
+            *authorised = console::token_matches(given, &token);
+            if *authorised {
+                info!(target: "server", "Console client authenticated");
+                return Some(console::ack(name, &Ok("authenticated".to_string())));
+            }
+            warn!(target: "server", "Console client sent a wrong token");
+            return Some(console::ack(name, &Err("wrong token".to_string())));
+        }
+        if !*authorised {
+            warn!(target: "server", "Rejected console command {} from an unauthenticated client", name);
+            return Some(console::ack(name, &Err("not authenticated".to_string())));
+        }
+
+        if let Ok(mut queue) = queue.lock() {
+            queue.push_back((json.clone(), ack_sender.clone()));
+        }
+        None
+    }
+
+    // Queues a parsed message and records its sensor reading against the current session.
+    pub fn push_action(action_queue: &Arc<Mutex<VecDeque<GameAction>>>, metrics: &Option<SharedMetrics>, (action, value): (GameAction, Option<f64>)) {
+        if let (Some(value), Some(metrics)) = (value, metrics) {
+            if let Ok(mut metrics) = metrics.lock() {
+                metrics.peak(&action, value);
+            }
+        }
+        if let Ok(mut queue) = action_queue.lock() {
+            queue.push_back(action);
+        }
+    }
+
+    // Turns one controller message (`{"version":1,"action":"peakleft","value":...}`) into a GameAction and its
+    // sensor value. Shared by every transport that speaks the JSON protocol.
+    pub fn parse_action(text: &str) -> Result<(GameAction, Option<f64>), ProtocolError> {
+        let message = ControllerMessage::parse(text)?;
+        trace!(target: "server", "{:?}", message);
+        Ok(message.action())
+    }
+}
+
+// #include <WiFiS3.h>
+// #include <WebSocketsClient.h>
+// #include <ArduinoJson.h>
+
+// const char* ssid = "gooddogL";
+// const char* password = "eatbadman";
+
+// const char* websocket_server = "192.168.1.122";
+// const int websocket_port = 3030;
+
+// const int sensorPin = A0;
+
+// WebSocketsClient webSocket;
+// bool isConnected = false;
+
+// void setup() {
+//     Serial.begin(9600);
+//     while (!Serial); 
+//     delay(2000);     
+
+//     Serial.println("Attempting to connect to WiFi...");
+//     int status = WL_IDLE_STATUS;
+//     int retries = 0;
+//     while (status != WL_CONNECTED && retries < 10) {
+//         status = WiFi.begin(ssid, password);
+//         delay(1000);
+//         retries++;
+//         Serial.print("WiFi connection attempt ");
+//         Serial.println(retries);
+//     }
+
+//     if (status != WL_CONNECTED) {
+//         Serial.println("Failed to connect to WiFi.");
+//         return;
+//     }
+
+//     Serial.println("Initializing WebSocket connection...");
+//     webSocket.begin(websocket_server, websocket_port, "/");
+//     webSocket.onEvent(webSocketEvent);
+//     webSocket.setReconnectInterval(5000);  
+// }
+
+// // WebSocket event handler
+// void webSocketEvent(WStype_t type, uint8_t * payload, size_t length) {
+//     switch (type) {
+//         case WStype_DISCONNECTED:
+//             isConnected = false;
+//             Serial.println("WebSocket disconnected.");
+//             break;
+
+//         case WStype_CONNECTED:
+//             isConnected = true;
+//             Serial.println("WebSocket connected.");
+//             break;
+
+//         case WStype_TEXT:
+//             Serial.print("Received text: ");
+//             Serial.write(payload, length);
+//             Serial.println();
+//             break;
+
+//         case WStype_BIN:
+//             Serial.println("Received binary data (not used).");
+//             break;
+
+//         case WStype_ERROR:
+//             isConnected = false;
+//             Serial.println("WebSocket error occurred.");
+//             break;
+
+//         case WStype_PING:
+//             Serial.println("WebSocket ping received.");
+//             break;
+
+//         case WStype_PONG:
+//             Serial.println("WebSocket pong received.");
+//             break;
+
+//         default:
+//             Serial.println("Unknown WebSocket event.");
+//             break;
+//     }
+// }
+
+// void sendSensorData(int sensorValue) {
+//     if (!isConnected) {
+//         Serial.println("Not connected. Skipping data send.");
+//         return;
+//     }
+
+//     StaticJsonDocument<200> doc;
+//     doc["action"] = "left";          
+//     doc["value"] = sensorValue;       
+
+//     String jsonString;
+//     serializeJson(doc, jsonString);
+
+//     Serial.print("Sending JSON: ");
+//     Serial.println(jsonString);
+
+//     webSocket.sendTXT(jsonString);   
+// }
+
+// void loop() {
+//     webSocket.loop();                     
+
+//     int sensorValue = analogRead(sensorPin);
+//     Serial.print("Sensor value: ");
+//     Serial.println(sensorValue);
+
+//     sendSensorData(sensorValue);
+// }
@@ -1,4 +1,5 @@
 mod game_collection;
+mod logger;
 
 use pelican_ui::{Context, Plugins, Plugin, maverick_start, start, Application, PelicanEngine, MaverickOS, HardwareContext, runtime::Services};
 use pelican_ui::drawable::Drawable;
@@ -16,9 +17,9 @@ use pelican_ui::include_assets;
 use std::sync::{Arc, Mutex};
 use std::collections::VecDeque;
 
-use crate::game_collection::galaga_game::galaga::Galaga;
-use crate::game_collection::galaga_game::player::Player;
-use crate::game_collection::galaga_game::server::{ArduinoServer, GameAction};
+use crate::game_collection::registry;
+use crate::game_collection::launcher::Launcher;
+use crate::game_collection::assets::AssetManifest;
 
 pub struct MyApp;
 
@@ -36,33 +37,20 @@ impl Plugins for MyApp {
 
 impl Application for MyApp {
     async fn new(ctx: &mut Context) -> Box<dyn Drawable> {
-        
+        logger::init(log::LevelFilter::Info);
+
         ctx.assets.include_assets(include_assets!("./assets"));
-        let mut illustrations = ctx.theme.brand.illustrations.clone();
-        illustrations.insert(ctx, "spaceship", "spaceship.png");
-        illustrations.insert(ctx, "b2", "b2.png");
-        illustrations.insert(ctx, "tiki_fly", "tiki_fly.png");
-        illustrations.insert(ctx, "northrop", "northrop.png");
-        illustrations.insert(ctx, "bullet_downward", "bullet_downward.png");
-        illustrations.insert(ctx, "bullet_blue", "bullet_blue.png");
-        illustrations.insert(ctx, "explosion", "explosion.png");
-        ctx.theme.brand.illustrations = illustrations;
+        // Manifests are checked by the assets test; one that still fails here only loses that game's sprites.
+        for game in registry() {
+            match AssetManifest::load(game.as_ref()) {
+                Ok(manifest) => manifest.register(ctx),
+                Err(e) => log::error!(target: "assets", "{}", e),
+            }
+        }
 
-        let game = Games::Galaga.init(ctx);
-        Box::new(Interface::new(ctx, game, None))
+        let launcher = Box::new(Launcher::new(ctx));
+        Box::new(Interface::new(ctx, launcher, None))
     }
 }
 
-start!(MyApp);
-
-enum Games {
-    Galaga
-}
-
-impl Games {
-    pub fn init(&self, ctx: &mut Context) -> Box<dyn AppPage> {
-        match self {
-            Games::Galaga => Box::new(Galaga::new(ctx, None))
-        }
-    }
-}
\ No newline at end of file
+start!(MyApp);
\ No newline at end of file
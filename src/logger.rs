@@ -0,0 +1,104 @@
+use log::{LevelFilter, Log, Metadata, Record};
+use once_cell::sync::Lazy;
+use std::fs::{self, File, OpenOptions};
+use std::io::Write;
+use std::path::PathBuf;
+use std::sync::Mutex;
+use std::time::{Instant, SystemTime, UNIX_EPOCH};
+
+// Levels the settings page cycles through, in order.
+pub const LEVELS: [LevelFilter; 6] = [
+    LevelFilter::Off,
+    LevelFilter::Error,
+    LevelFilter::Warn,
+    LevelFilter::Info,
+    LevelFilter::Debug,
+    LevelFilter::Trace,
+];
+
+static START: Lazy<Instant> = Lazy::new(Instant::now);
+static LOGGER: SessionLogger = SessionLogger(Mutex::new(None));
+
+// Writes every record to stderr and, while a session file is open, to that file as well.
+struct SessionLogger(Mutex<Option<(PathBuf, File)>>);
+
+impl Log for SessionLogger {
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        metadata.level() <= log::max_level()
+    }
+
+    fn log(&self, record: &Record) {
+        if !self.enabled(record.metadata()) { return; }
+        let line = format!("[{:>9.3}s {:<5} {}] {}", START.elapsed().as_secs_f32(), record.level(), record.target(), record.args());
+        eprintln!("{}", line);
+        if let Ok(mut file) = self.0.lock() {
+            if let Some((_, file)) = file.as_mut() {
+                let _ = writeln!(file, "{}", line);
+            }
+        }
+    }
+
+    fn flush(&self) {
+        if let Ok(mut file) = self.0.lock() {
+            if let Some((_, file)) = file.as_mut() {
+                let _ = file.flush();
+            }
+        }
+    }
+}
+
+pub fn init(level: LevelFilter) {
+    Lazy::force(&START);
+    if log::set_logger(&LOGGER).is_ok() {
+        log::set_max_level(level);
+    }
+}
+
+pub fn level() -> LevelFilter {
+    log::max_level()
+}
+
+pub fn set_level(level: LevelFilter) {
+    log::set_max_level(level);
+    log::info!(target: "galaga", "log level set to {}", level);
+}
+
+// Advances to the next level in LEVELS, wrapping from Trace back to Off.
+pub fn cycle_level() -> LevelFilter {
+    let index = LEVELS.iter().position(|l| *l == level()).unwrap_or(0);
+    let next = LEVELS[(index + 1) % LEVELS.len()];
+    set_level(next);
+    next
+}
+
+// Session logs live under Documents so they can be pulled off the device after a clinic session.
+pub fn log_dir() -> PathBuf {
+    let home = std::env::var_os("HOME").map(PathBuf::from).unwrap_or_else(std::env::temp_dir);
+    home.join("Documents").join("ramp_games").join("logs")
+}
+
+pub fn session_file() -> Option<PathBuf> {
+    LOGGER.0.lock().ok().and_then(|f| f.as_ref().map(|(p, _)| p.clone()))
+}
+
+pub fn start_session_file() -> std::io::Result<PathBuf> {
+    let dir = log_dir();
+    fs::create_dir_all(&dir)?;
+    let stamp = SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_secs()).unwrap_or(0);
+    let path = dir.join(format!("session-{}.log", stamp));
+    let file = OpenOptions::new().create(true).append(true).open(&path)?;
+    if let Ok(mut current) = LOGGER.0.lock() {
+        *current = Some((path.clone(), file));
+    }
+    log::info!(target: "galaga", "session log started at {}", path.display());
+    Ok(path)
+}
+
+pub fn stop_session_file() -> Option<PathBuf> {
+    log::info!(target: "galaga", "session log stopped");
+    LOGGER.0.lock().ok().and_then(|mut f| f.take()).map(|(path, mut file)| {
+        let _ = file.flush();
+        path
+    })
+}
+